@@ -35,6 +35,37 @@ format_day_number() {
     return 0
 }
 
+# Format one language's "timings_ms" as a markdown table row
+# Usage: format_timings_row <language> <stdout_file>
+# Prints "| language | runs | parse | part1 | part2 |" with min/median/max in
+# milliseconds, or "-" cells when the output has no timings
+format_timings_row() {
+    local language="$1"
+    local stdout_file="$2"
+
+    python3 - "$language" "$stdout_file" << 'PYEOF' 2>/dev/null || echo "| $language | - | - | - | - |"
+import json
+import sys
+
+language, path = sys.argv[1], sys.argv[2]
+timings = None
+try:
+    with open(path) as f:
+        timings = json.load(f).get("timings_ms")
+except (OSError, ValueError, AttributeError):
+    pass
+
+def cell(phase):
+    stats = (timings or {}).get(phase)
+    if not isinstance(stats, dict):
+        return "-"
+    return "{} / {} / {}".format(stats.get("min"), stats.get("median"), stats.get("max"))
+
+runs = timings.get("runs", "-") if isinstance(timings, dict) else "-"
+print("| {} | {} | {} | {} | {} |".format(language, runs, cell("parse"), cell("part1"), cell("part2")))
+PYEOF
+}
+
 # Generate markdown report from test results
 # Usage: generate_markdown_report <day_number> <test_output_dir> <report_file>
 # Creates a markdown file with test results, commonalities, and differences
@@ -135,6 +166,22 @@ generate_markdown_report() {
         echo "---"
         echo ""
 
+        # Timings section, filled from the "timings_ms" field that benchmark
+        # runs add to their JSON output (e.g. Rust with BENCH_RUNS set)
+        echo "## Timings"
+        echo ""
+        echo "Min / median / max in milliseconds; \`-\` means the language did not report timings."
+        echo ""
+        echo "| Language | Runs | Parse | Part 1 | Part 2 |"
+        echo "|----------|------|-------|--------|--------|"
+        for language in "${VALIDATION_LANGUAGES[@]}"; do
+            format_timings_row "$language" "${test_output_dir}/${language}_stdout.txt"
+        done
+        echo ""
+
+        echo "---"
+        echo ""
+
         # Cross-language validation section
         echo "## Cross-Language Validation"
         echo ""
@@ -3,7 +3,7 @@
 # Rust test runner for Advent of Code solutions
 # Executes Rust unit tests and integration tests
 # Unit test: cargo test
-# Integration test: cat <input_path> | cargo run
+# Integration test: aoc run --day <day_number> --input <input_path>
 
 set -eo pipefail
 
@@ -27,14 +27,21 @@ Arguments:
   input_path    Path to input file for integration test
   --unit-test   Run unit tests only (optional)
 
+Environment:
+  BENCH_RUNS    Solve the input this many times and add min/median/max
+                timings to the JSON output as "timings_ms"
+
 Examples:
   $0 1 challenges/day01/input.txt          # Run integration test
+  BENCH_RUNS=10 $0 1 challenges/day01/input.txt  # Run and report timings
   $0 1 /dev/null --unit-test               # Run unit tests only
 
 Exit codes:
   0  - Tests passed
   1  - Tests failed or error occurred
   2  - Missing arguments or invalid input
+  3-6 - Solver error (I/O, parse, infeasible, timeout), passed through from
+        the aoc binary; see solutions/rust/aoc-core/src/error.rs
 EOF
 }
 
@@ -137,7 +144,7 @@ if [[ "$UNIT_TEST_ONLY" == true ]]; then
     exit $exit_code
 fi
 
-# Run integration test (cargo run with input file)
+# Run integration test (aoc binary with input file)
 if [[ ! -f "$INPUT_PATH" ]]; then
     log_error "Input file not found: $INPUT_PATH"
     echo '{"part1": null, "part2": null}'
@@ -146,26 +153,38 @@ fi
 
 log_info "Running Rust integration test for $DAY_FORMATTED with input: $INPUT_PATH" >&2
 
-# Run cargo and capture output (pipe input file to stdin)
+# Run the workspace-wide `aoc` binary, which dispatches to the day in-process
 # --quiet to suppress cargo build output
 # --release for optimized build
+# Only stdout is captured: it carries just the JSON answer, while solver logs
+# (level set by AOC_LOG) and cargo errors go to stderr
+# BENCH_RUNS adds --bench, which reports per-phase timings as "timings_ms"
+AOC_ARGS=(run --day "$DAY_NUMBER" --input "$INPUT_PATH")
+if [[ -n "${BENCH_RUNS:-}" ]]; then
+    AOC_ARGS+=(--bench "$BENCH_RUNS")
+fi
+
 set +e
-OUTPUT=$(cat "$INPUT_PATH" | cargo run --release 2>&1)
+OUTPUT=$(cd "${PROJECT_ROOT}/solutions/rust" && cargo run --release --quiet -p aoc -- "${AOC_ARGS[@]}")
 EXIT_CODE=$?
 set -e
 
-# Check for compilation errors
+# Exit codes 3-6 are solver errors: the aoc binary has already printed a JSON
+# line with an "error" object, so pass that and the exit code through
+if [[ $EXIT_CODE -ge 3 ]] && [[ $EXIT_CODE -le 6 ]]; then
+    log_error "Rust solver reported an error (exit code: $EXIT_CODE)"
+    echo "$OUTPUT"
+    exit $EXIT_CODE
+fi
+
+# Anything else (build failure, usage error, panic) has no usable JSON
 if [[ $EXIT_CODE -ne 0 ]]; then
     log_error "Rust execution failed with exit code: $EXIT_CODE"
-    # Print error to stderr for debugging
-    echo "$OUTPUT" >&2
     # Output null JSON to stdout for consistency
     echo '{"part1": null, "part2": null}'
     exit 1
 fi
 
-# Extract JSON from output (should be on stdout)
-# The solution should print JSON to stdout
 echo "$OUTPUT"
 
 exit 0
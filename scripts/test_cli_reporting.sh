@@ -291,6 +291,46 @@ test_report_divergences() {
     rm -rf "$test_output_dir"
 }
 
+# Test 7: Report includes timings from "timings_ms"
+test_report_timings() {
+    log_info "Test 7: Report includes timings from timings_ms"
+
+    # Source report generation functions
+    source "${SCRIPT_DIR}/generate_report.sh"
+
+    # Create a temporary test output directory
+    local test_output_dir
+    test_output_dir=$(mktemp -d -t test-cli-timings.XXXXXX)
+
+    # Rust reports timings, the other languages do not
+    echo '{"part1": 1, "part2": 2, "timings_ms": {"runs": 5, "parse": {"min": 0.1, "median": 0.2, "max": 0.3}, "part1": {"min": 1.0, "median": 1.5, "max": 2.0}, "part2": {"min": 3.0, "median": 3.5, "max": 4.0}}}' > "${test_output_dir}/rust_stdout.txt"
+    echo "" > "${test_output_dir}/rust_stderr.txt"
+    echo "0" > "${test_output_dir}/rust_exit_code.txt"
+    for lang in "gleam" "roc" "carbon" "bosque"; do
+        echo '{"part1": 1, "part2": 2}' > "${test_output_dir}/${lang}_stdout.txt"
+        echo "" > "${test_output_dir}/${lang}_stderr.txt"
+        echo "0" > "${test_output_dir}/${lang}_exit_code.txt"
+    done
+
+    # Generate test report
+    local report_file="${test_output_dir}/test_report.md"
+    set +e
+    generate_markdown_report "4" "$test_output_dir" "$report_file"
+    set -e
+
+    if [[ -f "$report_file" ]]; then
+        local report_content
+        report_content=$(cat "$report_file")
+
+        assert_contains "$report_content" "## Timings" "Report has a timings section"
+        assert_contains "$report_content" "| rust | 5 | 0.1 / 0.2 / 0.3 | 1.0 / 1.5 / 2.0 | 3.0 / 3.5 / 4.0 |" "Report shows Rust timings"
+        assert_contains "$report_content" "| gleam | - | - | - | - |" "Report marks languages without timings"
+    fi
+
+    # Cleanup
+    rm -rf "$test_output_dir"
+}
+
 # Run all tests
 main() {
     log_info "========================================"
@@ -310,6 +350,8 @@ main() {
     echo
     test_report_divergences
     echo
+    test_report_timings
+    echo
 
     log_info "========================================"
     log_info "Test Results Summary"
@@ -0,0 +1,146 @@
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::runner::Answers;
+use crate::{AocError, Solution};
+
+/// Minimum, median and maximum of a phase's run times, in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub max: f64,
+}
+
+impl Stats {
+    /// Summarize `samples`, which must not be empty.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut millis: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+        millis.sort_by(f64::total_cmp);
+        let mid = millis.len() / 2;
+        let median = if millis.len().is_multiple_of(2) {
+            (millis[mid - 1] + millis[mid]) / 2.0
+        } else {
+            millis[mid]
+        };
+        Stats {
+            min: round_micros(millis[0]),
+            median: round_micros(median),
+            max: round_micros(millis[millis.len() - 1]),
+        }
+    }
+}
+
+/// Keep three decimals: timer noise makes finer digits meaningless.
+fn round_micros(millis: f64) -> f64 {
+    (millis * 1000.0).round() / 1000.0
+}
+
+/// Per-phase timings of a `--bench` run, printed as `"timings_ms"`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Timings {
+    pub runs: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+/// Solve `input` `runs` times (at least once), timing parse, part 1 and
+/// part 2 separately. The answers come from the last run.
+pub fn bench<S: Solution>(input: &str, runs: usize) -> Result<Answers, AocError> {
+    let runs = runs.max(1);
+    let mut parse = Vec::with_capacity(runs);
+    let mut part1 = Vec::with_capacity(runs);
+    let mut part2 = Vec::with_capacity(runs);
+    let mut last = None;
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = S::parse(input)?;
+        parse.push(start.elapsed());
+
+        let start = Instant::now();
+        let answer1 = S::part1(&parsed).map(Into::into);
+        part1.push(start.elapsed());
+
+        let start = Instant::now();
+        let answer2 = S::part2(&parsed).map(Into::into);
+        part2.push(start.elapsed());
+
+        last = Some((parsed, answer1, answer2));
+    }
+
+    let (parsed, part1_answer, part2_answer) = last.expect("at least one run");
+    Ok(Answers {
+        part1: part1_answer,
+        part2: part2_answer,
+        additional_info: S::additional_info(&parsed),
+        timings: Some(Timings {
+            runs,
+            parse: Stats::from_samples(&parse),
+            part1: Stats::from_samples(&part1),
+            part2: Stats::from_samples(&part2),
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+
+    struct Count;
+
+    impl Solution for Count {
+        const DAY: u8 = 0;
+        type Input = usize;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> Result<usize, AocError> {
+            Ok(input.lines().count())
+        }
+
+        fn part1(lines: &usize) -> Result<usize, AocError> {
+            Ok(*lines)
+        }
+
+        fn part2(lines: &usize) -> Result<usize, AocError> {
+            Ok(lines * 2)
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+        let odd = Stats::from_samples(&[ms(3), ms(1), ms(2)]);
+        assert_eq!((odd.min, odd.median, odd.max), (1.0, 2.0, 3.0));
+        let even = Stats::from_samples(&[ms(4), ms(1), ms(2), ms(3)]);
+        assert_eq!(even.median, 2.5);
+        let single = Stats::from_samples(&[Duration::from_nanos(1_234_567)]);
+        assert_eq!(single.min, 1.235);
+    }
+
+    #[test]
+    fn test_bench_reports_answers_and_timings() {
+        let answers = bench::<Count>("a\nb\n", 5).unwrap();
+        assert_eq!(answers.part1, Ok(Answer::Integer(2)));
+        assert_eq!(answers.part2, Ok(Answer::Integer(4)));
+        let timings = answers.timings.unwrap();
+        assert_eq!(timings.runs, 5);
+        assert!(timings.part1.min <= timings.part1.median);
+        assert!(timings.part1.median <= timings.part1.max);
+
+        let json = answers.to_json();
+        assert!(json.starts_with(
+            "{\"part1\": 2, \"part2\": 4, \"timings_ms\": {\"runs\": 5, \"parse\": {\"min\": "
+        ));
+    }
+
+    #[test]
+    fn test_zero_runs_still_solves_once() {
+        let answers = bench::<Count>("a\n", 0).unwrap();
+        assert_eq!(answers.timings.unwrap().runs, 1);
+    }
+}
@@ -0,0 +1,199 @@
+//! The error type shared by every day, and how each variant is reported.
+//!
+//! | Variant           | JSON output                                                | Exit code |
+//! |-------------------|------------------------------------------------------------|-----------|
+//! | (no error)        | both answers                                               | 0         |
+//! | `UnsupportedPart` | that part is `null`, no `"error"` field                    | 0         |
+//! | `Io`              | both parts `null`, `"error": {"kind": "io", ...}`          | 3         |
+//! | `Parse`           | failed part(s) `null`, `"error": {"kind": "parse", ...}`   | 4         |
+//! | `Infeasible`      | failed part `null`, `"error": {"kind": "infeasible", ...}` | 5         |
+//! | `Timeout`         | failed part `null`, `"error": {"kind": "timeout", ...}`    | 6         |
+//!
+//! Exit code 2 is reserved for command-line usage errors and 101 is Rust's
+//! panic exit code, so any other non-zero status means the solver crashed.
+//! When both parts fail, part 1's error decides the `"error"` field and the
+//! exit code.
+
+use std::fmt;
+use std::io;
+
+use serde::Serialize;
+
+/// Why a day could not produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AocError {
+    /// The input could not be read.
+    Io { message: String },
+    /// The input is malformed. `line` and `column` are 1-based when known.
+    Parse {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        line: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        column: Option<usize>,
+        message: String,
+    },
+    /// The input is well-formed but has no answer: no solution exists, or it
+    /// does not fit the answer type.
+    Infeasible { message: String },
+    /// The solver gave up before finding an answer.
+    Timeout { message: String },
+    /// The day does not implement (or the puzzle does not have) this part.
+    UnsupportedPart { part: u8 },
+}
+
+impl AocError {
+    /// Parse error that is not tied to a position in the input.
+    pub fn parse(message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    /// Parse error on a 1-based line.
+    pub fn parse_at_line(line: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line: Some(line),
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    /// Parse error at a 1-based line and column.
+    pub fn parse_at(line: usize, column: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line: Some(line),
+            column: Some(column),
+            message: message.into(),
+        }
+    }
+
+    /// Parse error at byte `offset` into `input`, converted to line and column.
+    pub fn parse_at_offset(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &input[..offset.min(input.len())];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        AocError::parse_at(line, column, message)
+    }
+
+    pub fn infeasible(message: impl Into<String>) -> Self {
+        AocError::Infeasible {
+            message: message.into(),
+        }
+    }
+
+    pub fn timeout(message: impl Into<String>) -> Self {
+        AocError::Timeout {
+            message: message.into(),
+        }
+    }
+
+    /// Process exit code for this error (see the module docs).
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AocError::UnsupportedPart { .. } => 0,
+            AocError::Io { .. } => 3,
+            AocError::Parse { .. } => 4,
+            AocError::Infeasible { .. } => 5,
+            AocError::Timeout { .. } => 6,
+        }
+    }
+
+    /// False for [`AocError::UnsupportedPart`], which is reported as a plain `null`.
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, AocError::UnsupportedPart { .. })
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io { message } => write!(f, "I/O error: {}", message),
+            AocError::Parse {
+                line,
+                column,
+                message,
+            } => match (line, column) {
+                (Some(line), Some(column)) => {
+                    write!(
+                        f,
+                        "parse error at line {}, column {}: {}",
+                        line, column, message
+                    )
+                }
+                (Some(line), None) => write!(f, "parse error at line {}: {}", line, message),
+                _ => write!(f, "parse error: {}", message),
+            },
+            AocError::Infeasible { message } => write!(f, "no solution: {}", message),
+            AocError::Timeout { message } => write!(f, "timed out: {}", message),
+            AocError::UnsupportedPart { part } => write!(f, "part {} is not supported", part),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::Io {
+            message: e.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            AocError::parse_at(3, 5, "expected a digit").to_string(),
+            "parse error at line 3, column 5: expected a digit"
+        );
+        assert_eq!(
+            AocError::parse_at_line(2, "empty").to_string(),
+            "parse error at line 2: empty"
+        );
+        assert_eq!(
+            AocError::UnsupportedPart { part: 2 }.to_string(),
+            "part 2 is not supported"
+        );
+    }
+
+    #[test]
+    fn test_parse_at_offset() {
+        let input = "11-22,\n95-x";
+        assert_eq!(
+            AocError::parse_at_offset(input, 10, "bad"),
+            AocError::parse_at(2, 4, "bad")
+        );
+        assert_eq!(
+            AocError::parse_at_offset(input, 0, "bad"),
+            AocError::parse_at(1, 1, "bad")
+        );
+    }
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            AocError::from(io::Error::other("boom")),
+            AocError::parse("bad"),
+            AocError::infeasible("none"),
+            AocError::timeout("slow"),
+        ];
+        let mut codes: Vec<i32> = errors.iter().map(AocError::exit_code).collect();
+        codes.dedup();
+        assert_eq!(codes, vec![3, 4, 5, 6]);
+        assert_eq!(AocError::UnsupportedPart { part: 1 }.exit_code(), 0);
+    }
+
+    #[test]
+    fn test_serialized_kind() {
+        let json = serde_json::to_string(&AocError::parse_at_line(7, "bad")).unwrap();
+        assert_eq!(json, r#"{"kind":"parse","line":7,"message":"bad"}"#);
+    }
+}
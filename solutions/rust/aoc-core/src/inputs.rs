@@ -0,0 +1,194 @@
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use crate::AocError;
+
+/// Compile-time location of `challenges/`, used when it cannot be found by
+/// walking up from the current directory.
+const FALLBACK_CHALLENGES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../challenges");
+
+/// Locate the repository's `challenges/` directory.
+///
+/// Searches the current directory and its ancestors first, so binaries work
+/// from the repo root, `solutions/rust` or a day crate alike, then falls back
+/// to the path of this checkout.
+pub fn challenges_dir() -> PathBuf {
+    if let Ok(cwd) = env::current_dir()
+        && let Some(dir) = find_challenges_dir(&cwd)
+    {
+        return dir;
+    }
+    PathBuf::from(FALLBACK_CHALLENGES_DIR)
+}
+
+fn find_challenges_dir(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join("challenges"))
+        .find(|candidate| candidate.is_dir())
+}
+
+/// Path of a day's puzzle input: `challenges/dayNN/input.txt`, or
+/// `input-sample.txt` when `sample` is set.
+pub fn input_path(day: u8, sample: bool) -> PathBuf {
+    let file = if sample {
+        "input-sample.txt"
+    } else {
+        "input.txt"
+    };
+    challenges_dir().join(format!("day{:02}", day)).join(file)
+}
+
+/// Where a day reads its puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+    /// `challenges/dayNN/input.txt`, or the sample input when `sample` is set.
+    Challenge {
+        sample: bool,
+    },
+}
+
+impl InputSource {
+    /// Parse a day binary's arguments: nothing (stdin), `--input <PATH|->`
+    /// or `--sample`.
+    pub fn from_args<'a>(args: impl IntoIterator<Item = &'a str>) -> Result<Self, String> {
+        let mut args = args.into_iter();
+        let mut source = None;
+        while let Some(arg) = args.next() {
+            let next = match arg {
+                "--input" => match args.next() {
+                    Some("-") => InputSource::Stdin,
+                    Some(path) => InputSource::File(PathBuf::from(path)),
+                    None => return Err("--input needs a path (or - for stdin)".to_string()),
+                },
+                "--sample" => InputSource::Challenge { sample: true },
+                other => return Err(format!("Unknown argument '{}'", other)),
+            };
+            if source.replace(next).is_some() {
+                return Err("--input and --sample can only be given once".to_string());
+            }
+        }
+        Ok(source.unwrap_or(InputSource::Stdin))
+    }
+
+    /// Name of the input in error messages: the file path, or `stdin`.
+    pub fn name(&self, day: u8) -> String {
+        match self {
+            InputSource::Stdin => "stdin".to_string(),
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Challenge { sample } => input_path(day, *sample).display().to_string(),
+        }
+    }
+
+    /// Read the whole input for `day`. Errors name the file (or stdin).
+    pub fn read(&self, day: u8) -> Result<String, AocError> {
+        let io_error = |e: io::Error| AocError::Io {
+            message: format!("{}: {}", self.name(day), e),
+        };
+        match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map_err(io_error)?;
+                Ok(input)
+            }
+            InputSource::File(path) => fs::read_to_string(path).map_err(io_error),
+            InputSource::Challenge { sample } => {
+                fs::read_to_string(input_path(day, *sample)).map_err(io_error)
+            }
+        }
+    }
+
+    /// Open the input for `day` to read a line at a time instead of all at
+    /// once. Errors opening it name the file; errors while reading are for
+    /// the caller to report, with [`InputSource::name`].
+    pub fn open(&self, day: u8) -> Result<Box<dyn BufRead>, AocError> {
+        let open = |path: &Path| {
+            fs::File::open(path)
+                .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+                .map_err(|e| AocError::Io {
+                    message: format!("{}: {}", path.display(), e),
+                })
+        };
+        match self {
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::File(path) => open(path),
+            InputSource::Challenge { sample } => open(&input_path(day, *sample)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_points_at_challenges() {
+        assert!(Path::new(FALLBACK_CHALLENGES_DIR).join("day01").is_dir());
+    }
+
+    #[test]
+    fn test_find_from_nested_directory() {
+        let nested = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let found = find_challenges_dir(&nested).unwrap();
+        assert!(found.ends_with("challenges"));
+        assert!(found.join("day01").is_dir());
+    }
+
+    #[test]
+    fn test_sample_input_path() {
+        let path = input_path(7, true);
+        assert!(path.ends_with("day07/input-sample.txt"));
+        assert!(path.is_file());
+    }
+
+    #[test]
+    fn test_source_from_args() {
+        let parse = |args: &[&str]| InputSource::from_args(args.iter().copied());
+        assert_eq!(parse(&[]), Ok(InputSource::Stdin));
+        assert_eq!(parse(&["--input", "-"]), Ok(InputSource::Stdin));
+        assert_eq!(
+            parse(&["--input", "in.txt"]),
+            Ok(InputSource::File(PathBuf::from("in.txt")))
+        );
+        assert_eq!(
+            parse(&["--sample"]),
+            Ok(InputSource::Challenge { sample: true })
+        );
+        assert!(parse(&["--input"]).is_err());
+        assert!(parse(&["--sample", "--input", "in.txt"]).is_err());
+        assert!(parse(&["s"]).is_err());
+    }
+
+    #[test]
+    fn test_read_reports_path() {
+        let source = InputSource::File(PathBuf::from("/nonexistent/day99.txt"));
+        match source.read(99) {
+            Err(AocError::Io { message }) => {
+                assert!(message.starts_with("/nonexistent/day99.txt: "))
+            }
+            other => panic!("expected an I/O error, got {:?}", other),
+        }
+        let sample = InputSource::Challenge { sample: true }.read(1).unwrap();
+        assert!(!sample.is_empty());
+    }
+
+    #[test]
+    fn test_open_matches_read() {
+        let source = InputSource::Challenge { sample: true };
+        let mut streamed = String::new();
+        source
+            .open(1)
+            .unwrap()
+            .read_to_string(&mut streamed)
+            .unwrap();
+        assert_eq!(streamed, source.read(1).unwrap());
+
+        let missing = InputSource::File(PathBuf::from("/nonexistent/day99.txt"));
+        assert_eq!(missing.name(99), "/nonexistent/day99.txt");
+        assert!(matches!(missing.open(99), Err(AocError::Io { .. })));
+    }
+}
@@ -0,0 +1,69 @@
+//! Shared building blocks for the Advent of Code day crates.
+//!
+//! Every day crate is a library that implements [`Solution`]. The day binaries
+//! are thin wrappers that hand their input to [`run`] (or [`run_with_input`]),
+//! which parses the input, solves both parts and prints the JSON line expected
+//! by the test harness:
+//!
+//! ```text
+//! {"part1": 3, "part2": 6}
+//! ```
+//!
+//! With `--bench [N]` the runner solves the input N times and adds
+//! `"timings_ms"` with the min/median/max of parse, part 1 and part 2.
+//!
+//! Failures are reported through [`AocError`]; the [`error`] module documents
+//! how each kind maps to the JSON output and the process exit code.
+//! Diagnostics go to stderr through the [`log`] macros; see [`logging`].
+
+mod bench;
+pub mod error;
+mod inputs;
+pub mod logging;
+mod output;
+mod runner;
+pub mod samples;
+
+pub use bench::{Stats, Timings, bench};
+pub use error::AocError;
+pub use inputs::{InputSource, challenges_dir, input_path};
+pub use output::{Answer, Output, to_json};
+pub use runner::{
+    Answers, DEFAULT_BENCH_RUNS, RunOptions, fail, options, options_from, print_and_exit,
+    report_part_error, run, run_with_input, run_with_options, solve, solve_part, take_flag,
+    take_value, usage_error,
+};
+
+/// A single day's puzzle, split into a parse step and two independent parts.
+///
+/// Implementors are usually unit structs (`pub struct Day01;`) so that other
+/// crates can drive any day generically, e.g. `aoc_core::solve::<Day01>(input)`.
+pub trait Solution {
+    /// Puzzle day (1-25).
+    const DAY: u8;
+
+    /// Parsed representation of the puzzle input shared by both parts.
+    type Input;
+
+    /// Answer type for part 1.
+    type Part1: Into<Answer>;
+
+    /// Answer type for part 2.
+    type Part2: Into<Answer>;
+
+    /// Parse the raw puzzle input.
+    fn parse(input: &str) -> Result<Self::Input, AocError>;
+
+    /// Solve part 1 from the parsed input.
+    fn part1(input: &Self::Input) -> Result<Self::Part1, AocError>;
+
+    /// Solve part 2 from the parsed input.
+    fn part2(input: &Self::Input) -> Result<Self::Part2, AocError>;
+
+    /// Structured data reported under `"additional-info"`.
+    ///
+    /// Defaults to nothing; day04 uses it to report its grid visualization.
+    fn additional_info(_input: &Self::Input) -> Option<serde_json::Value> {
+        None
+    }
+}
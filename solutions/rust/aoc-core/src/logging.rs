@@ -0,0 +1,129 @@
+//! Leveled diagnostics on stderr.
+//!
+//! Crates log through the [`log`] macros (`log::info!`, `log::debug!`, ...) and
+//! never print diagnostics to stdout, which carries only the JSON answer.
+//! The level comes from the `AOC_LOG` environment variable (`off`, `error`,
+//! `warn`, `info`, `debug` or `trace`; default `warn`). On the command line
+//! each `-v` raises it by one level and `-q` silences logging entirely.
+
+use std::env;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Environment variable holding the default log level.
+pub const LOG_ENV_VAR: &str = "AOC_LOG";
+
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            // Module paths like `day12::solver` are shortened to the crate name
+            let target = record.target().split("::").next().unwrap_or_default();
+            eprintln!("{:<5} {}: {}", record.level(), target, record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Install the stderr logger at `level`. Calling it again only changes the level.
+pub fn init(level: LevelFilter) {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level);
+}
+
+/// The level named by `AOC_LOG`, or `warn` when it is unset or invalid.
+pub fn env_level() -> LevelFilter {
+    env::var(LOG_ENV_VAR)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(LevelFilter::Warn)
+}
+
+/// Remove `-v`/`--verbose` (repeatable, also `-vv`) and `-q`/`--quiet` from
+/// `args` and return `base` adjusted by them.
+pub fn take_verbosity(args: &mut Vec<String>, base: LevelFilter) -> LevelFilter {
+    let mut level = base;
+    args.retain(|arg| match arg.as_str() {
+        "-q" | "--quiet" => {
+            level = LevelFilter::Off;
+            false
+        }
+        "--verbose" => {
+            level = raise(level, 1);
+            false
+        }
+        flag if flag.len() > 1 && flag.starts_with('-') && flag[1..].chars().all(|c| c == 'v') => {
+            level = raise(level, flag.len() - 1);
+            false
+        }
+        _ => true,
+    });
+    level
+}
+
+/// Strip the verbosity flags from `args` and install the logger at the
+/// resulting level.
+pub fn init_from_args(args: &mut Vec<String>) {
+    init(take_verbosity(args, env_level()));
+}
+
+fn raise(level: LevelFilter, steps: usize) -> LevelFilter {
+    LevelFilter::iter()
+        .filter(|&l| l >= level)
+        .nth(steps)
+        .unwrap_or(LevelFilter::Trace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_take_verbosity() {
+        let mut args = strings(&["-v", "--input", "in.txt", "-v"]);
+        assert_eq!(
+            take_verbosity(&mut args, LevelFilter::Warn),
+            LevelFilter::Debug
+        );
+        assert_eq!(args, strings(&["--input", "in.txt"]));
+
+        let mut args = strings(&["-vvv"]);
+        assert_eq!(
+            take_verbosity(&mut args, LevelFilter::Warn),
+            LevelFilter::Trace
+        );
+        assert!(args.is_empty());
+
+        let mut args = strings(&["--sample", "-q"]);
+        assert_eq!(
+            take_verbosity(&mut args, LevelFilter::Info),
+            LevelFilter::Off
+        );
+        assert_eq!(args, strings(&["--sample"]));
+
+        let mut args = strings(&["-", "-x"]);
+        assert_eq!(
+            take_verbosity(&mut args, LevelFilter::Warn),
+            LevelFilter::Warn
+        );
+        assert_eq!(args.len(), 2);
+    }
+
+    #[test]
+    fn test_raise_saturates() {
+        assert_eq!(raise(LevelFilter::Off, 1), LevelFilter::Error);
+        assert_eq!(raise(LevelFilter::Debug, 5), LevelFilter::Trace);
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt;
+use std::io;
+
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::ser::Formatter;
+
+use crate::AocError;
+use crate::bench::Timings;
+
+/// A single part's answer as it appears in the JSON output.
+///
+/// Serialized untagged: integers as JSON numbers (including values beyond
+/// `i64`), strings as escaped JSON strings and [`Answer::Null`] as `null`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(u128),
+    String(String),
+    Null,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::BigInteger(n) => write!(f, "{}", n),
+            Answer::String(s) => write!(f, "{}", s),
+            Answer::Null => write!(f, "null"),
+        }
+    }
+}
+
+macro_rules! answer_from_signed {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Integer(n.into())
+            }
+        }
+    )*};
+}
+
+macro_rules! answer_from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                match i64::try_from(n) {
+                    Ok(n) => Answer::Integer(n),
+                    Err(_) => Answer::BigInteger(n as u128),
+                }
+            }
+        }
+    )*};
+}
+
+answer_from_signed!(i8, i16, i32, i64, u8, u16, u32);
+answer_from_unsigned!(u64, usize, u128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::String(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::String(s.to_string())
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Answer::Null, Into::into)
+    }
+}
+
+/// The JSON object printed for a day: `{"part1": ..., "part2": ...}` plus
+/// optional `"additional-info"`, `"timings_ms"` and `"error"` objects.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Output {
+    pub part1: Answer,
+    pub part2: Answer,
+    #[serde(rename = "additional-info", skip_serializing_if = "Option::is_none")]
+    pub additional_info: Option<Value>,
+    #[serde(rename = "timings_ms", skip_serializing_if = "Option::is_none")]
+    pub timings: Option<Timings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<AocError>,
+}
+
+impl Output {
+    /// Output with both parts `null`.
+    pub fn null() -> Self {
+        Output {
+            part1: Answer::Null,
+            part2: Answer::Null,
+            additional_info: None,
+            timings: None,
+            error: None,
+        }
+    }
+
+    /// Both parts `null`, used when the input cannot be read or parsed.
+    pub fn from_error(error: AocError) -> Self {
+        Output {
+            error: Some(error),
+            ..Output::null()
+        }
+    }
+
+    /// Single-line JSON, e.g. `{"part1": 3, "part2": null}`.
+    pub fn to_json(&self) -> String {
+        to_json(self)
+    }
+}
+
+/// Serialize `value` as single-line JSON with a space after each `:` and `,`,
+/// matching the expected-output files under `challenges/`.
+pub fn to_json<T: Serialize + ?Sized>(value: &T) -> String {
+    let mut buf = Vec::new();
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buf, SpacedFormatter);
+    value
+        .serialize(&mut serializer)
+        .expect("serializing answers to JSON cannot fail");
+    String::from_utf8(buf).expect("serde_json always writes UTF-8")
+}
+
+/// Compact formatter that writes `", "` between items and `": "` after keys.
+struct SpacedFormatter;
+
+impl Formatter for SpacedFormatter {
+    fn begin_array_value<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_key<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(b": ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_answer_conversions() {
+        assert_eq!(Answer::from(42_i32), Answer::Integer(42));
+        assert_eq!(Answer::from(7_usize), Answer::Integer(7));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInteger(u64::MAX as u128));
+        assert_eq!(Answer::from(Some(5_i64)), Answer::Integer(5));
+        assert_eq!(Answer::from(None::<i64>), Answer::Null);
+        assert_eq!(Answer::from("abc"), Answer::String("abc".to_string()));
+    }
+
+    #[test]
+    fn test_output_json() {
+        let output = Output {
+            part1: Answer::Integer(-3),
+            part2: Answer::BigInteger(u128::MAX),
+            additional_info: None,
+            timings: None,
+            error: None,
+        };
+        assert_eq!(
+            output.to_json(),
+            format!("{{\"part1\": -3, \"part2\": {}}}", u128::MAX)
+        );
+        assert_eq!(
+            Output::null().to_json(),
+            "{\"part1\": null, \"part2\": null}"
+        );
+        assert_eq!(
+            Output::from_error(AocError::parse_at(2, 4, "bad digit")).to_json(),
+            "{\"part1\": null, \"part2\": null, \"error\": \
+             {\"kind\": \"parse\", \"line\": 2, \"column\": 4, \"message\": \"bad digit\"}}"
+        );
+    }
+
+    #[test]
+    fn test_strings_are_escaped() {
+        let output = Output {
+            part1: Answer::from("say \"hi\"\\"),
+            part2: Answer::Null,
+            additional_info: Some(json!({"grid": "ab\ncd", "rows": [1, 2]})),
+            timings: None,
+            error: None,
+        };
+        let text = output.to_json();
+        assert_eq!(
+            text,
+            "{\"part1\": \"say \\\"hi\\\"\\\\\", \"part2\": null, \
+             \"additional-info\": {\"grid\": \"ab\\ncd\", \"rows\": [1, 2]}}"
+        );
+        let parsed: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(parsed["additional-info"]["grid"], "ab\ncd");
+    }
+}
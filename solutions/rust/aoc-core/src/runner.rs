@@ -0,0 +1,417 @@
+use std::env;
+use std::process;
+
+use serde_json::Value;
+
+use crate::bench::{self, Timings};
+use crate::output::{Answer, Output};
+use crate::{AocError, InputSource, Solution, logging};
+
+/// Answers for both parts of a day.
+///
+/// A part that failed holds its error instead of an answer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answers {
+    pub part1: Result<Answer, AocError>,
+    pub part2: Result<Answer, AocError>,
+    pub additional_info: Option<Value>,
+    /// Per-phase timings, set only by a `--bench` run.
+    pub timings: Option<Timings>,
+}
+
+impl Answers {
+    /// True unless a part failed. An unsupported part is not a failure.
+    pub fn is_ok(&self) -> bool {
+        self.error().is_none()
+    }
+
+    /// The error that decides the exit code: part 1's failure, else part 2's.
+    pub fn error(&self) -> Option<&AocError> {
+        [&self.part1, &self.part2]
+            .into_iter()
+            .filter_map(|part| part.as_ref().err())
+            .find(|e| e.is_failure())
+    }
+
+    /// Process exit code for these answers (see [`crate::error`]).
+    pub fn exit_code(&self) -> i32 {
+        self.error().map_or(0, AocError::exit_code)
+    }
+
+    /// The printable output. Failed and unsupported parts are reported as `null`.
+    pub fn output(&self) -> Output {
+        Output {
+            part1: self.part1.clone().unwrap_or(Answer::Null),
+            part2: self.part2.clone().unwrap_or(Answer::Null),
+            additional_info: self.additional_info.clone(),
+            timings: self.timings,
+            error: self.error().cloned(),
+        }
+    }
+
+    /// Render the answers as the single-line JSON object read by the test harness.
+    pub fn to_json(&self) -> String {
+        self.output().to_json()
+    }
+
+    /// Log every part's error, prefixed with the day number. Unsupported
+    /// parts are logged at info level since they are not failures.
+    pub fn report_errors(&self, day: u8) {
+        for (part, result) in [(1, &self.part1), (2, &self.part2)] {
+            if let Err(e) = result {
+                report_part_error(day, part, e);
+            }
+        }
+    }
+}
+
+/// Log a part's error at error level, or info level for an unsupported part.
+pub fn report_part_error(day: u8, part: u8, error: &AocError) {
+    let level = if error.is_failure() {
+        log::Level::Error
+    } else {
+        log::Level::Info
+    };
+    log::log!(level, "Day {:02} part {}: {}", day, part, error);
+}
+
+/// Parse `input` and solve both parts of `S` in-process.
+///
+/// Returns an error only when the input cannot be parsed; failures in an
+/// individual part are kept in the returned [`Answers`].
+pub fn solve<S: Solution>(input: &str) -> Result<Answers, AocError> {
+    let parsed = S::parse(input)?;
+    Ok(Answers {
+        part1: S::part1(&parsed).map(Into::into),
+        part2: S::part2(&parsed).map(Into::into),
+        additional_info: S::additional_info(&parsed),
+        timings: None,
+    })
+}
+
+/// Parse `input` and solve a single part (1 or 2) of `S` in-process.
+///
+/// Parse failures and failures of the part itself are both returned as errors.
+pub fn solve_part<S: Solution>(input: &str, part: u8) -> Result<Answer, AocError> {
+    let parsed = S::parse(input)?;
+    match part {
+        1 => S::part1(&parsed).map(Into::into),
+        2 => S::part2(&parsed).map(Into::into),
+        _ => Err(AocError::UnsupportedPart { part }),
+    }
+}
+
+/// Default number of runs for `--bench` without a count.
+pub const DEFAULT_BENCH_RUNS: usize = 10;
+
+/// Command-line options shared by every day binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOptions {
+    pub source: InputSource,
+    /// `Some(n)` with `--bench [N]`: solve `n` times and report timings.
+    pub bench_runs: Option<usize>,
+}
+
+impl RunOptions {
+    /// Parse a day binary's arguments, after the logging flags are removed:
+    /// an input source (see [`InputSource::from_args`]) and `--bench [N]`.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut rest = Vec::new();
+        let mut bench_runs = None;
+        let mut args = args.iter().map(String::as_str).peekable();
+        while let Some(arg) = args.next() {
+            if arg != "--bench" {
+                rest.push(arg);
+                continue;
+            }
+            let runs = match args.peek().map(|next| next.parse::<usize>()) {
+                Some(Ok(runs)) => {
+                    args.next();
+                    runs
+                }
+                _ => DEFAULT_BENCH_RUNS,
+            };
+            if runs == 0 {
+                return Err("--bench needs at least one run".to_string());
+            }
+            bench_runs = Some(runs);
+        }
+        Ok(RunOptions {
+            source: InputSource::from_args(rest)?,
+            bench_runs,
+        })
+    }
+
+    /// Read the input, exiting through [`fail`] when it cannot be read.
+    pub fn read_input(&self, day: u8) -> String {
+        self.source.read(day).unwrap_or_else(|e| fail(day, e))
+    }
+}
+
+/// Remove a day-specific switch such as `--lenient` from the arguments,
+/// returning whether it was given.
+pub fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    match args.iter().position(|arg| arg == flag) {
+        Some(index) => {
+            args.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Remove a day-specific `flag <VALUE>` pair from the arguments, returning
+/// the value. A missing value is an error.
+pub fn take_value(args: &mut Vec<String>, flag: &str) -> Result<Option<String>, String> {
+    let Some(index) = args.iter().position(|arg| arg == flag) else {
+        return Ok(None);
+    };
+    args.remove(index);
+    if index >= args.len() {
+        return Err(format!("{} needs a value", flag));
+    }
+    Ok(Some(args.remove(index)))
+}
+
+/// Parse the process arguments for a day binary and set up logging from
+/// `-v`/`-q` (see [`crate::logging`]). Bad arguments exit with code 2.
+pub fn options(day: u8) -> RunOptions {
+    options_from(day, env::args().skip(1).collect(), "")
+}
+
+/// [`options`] for a binary with flags of its own: `args` are the process
+/// arguments left after it has taken those out, and `extra_usage` lists them
+/// for the usage line.
+pub fn options_from(day: u8, mut args: Vec<String>, extra_usage: &str) -> RunOptions {
+    logging::init_from_args(&mut args);
+    RunOptions::from_args(&args).unwrap_or_else(|e| usage_error(day, &e, extra_usage))
+}
+
+/// Print `message` and the day binary's usage line to stderr and exit with
+/// code 2.
+pub fn usage_error(day: u8, message: &str, extra_usage: &str) -> ! {
+    let extra = if extra_usage.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", extra_usage)
+    };
+    eprintln!(
+        "{}\n\nUsage: day{:02} [-v | -q] [--input <PATH|-> | --sample] [--bench [N]]{}",
+        message, day, extra
+    );
+    process::exit(2);
+}
+
+/// Read the puzzle input named on the command line (stdin by default),
+/// solve it and print the JSON answers.
+pub fn run<S: Solution>() {
+    let options = options(S::DAY);
+    let input = options.read_input(S::DAY);
+    run_with_options::<S>(&options, &input);
+}
+
+/// Solve already-loaded input as `options` asks: once, or repeatedly with
+/// timings under `--bench`.
+pub fn run_with_options<S: Solution>(options: &RunOptions, input: &str) {
+    match options.bench_runs {
+        Some(runs) => print_and_exit(S::DAY, bench::bench::<S>(input, runs)),
+        None => run_with_input::<S>(input),
+    }
+}
+
+/// Solve already-loaded input and print the JSON answers.
+///
+/// Exits with the code of the first failure, as documented in [`crate::error`].
+pub fn run_with_input<S: Solution>(input: &str) {
+    print_and_exit(S::DAY, solve::<S>(input));
+}
+
+/// Print answers produced outside [`run_with_input`] (by a day's own driver,
+/// say) and exit as it would: with the code of the first failure, or through
+/// [`fail`] when solving failed outright.
+pub fn print_and_exit(day: u8, result: Result<Answers, AocError>) {
+    match result {
+        Ok(answers) => {
+            answers.report_errors(day);
+            println!("{}", answers.to_json());
+            let code = answers.exit_code();
+            if code != 0 {
+                process::exit(code);
+            }
+        }
+        Err(e) => fail(day, e),
+    }
+}
+
+/// Report an error that prevented solving either part, print the `null`
+/// answers and exit with the error's exit code.
+pub fn fail(day: u8, error: AocError) -> ! {
+    log::error!("Day {:02}: {}", day, error);
+    let code = error.exit_code();
+    println!("{}", Output::from_error(error).to_json());
+    process::exit(code);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+        type Input = Vec<i64>;
+        type Part1 = i64;
+        type Part2 = i64;
+
+        fn parse(input: &str) -> Result<Self::Input, AocError> {
+            input
+                .split_whitespace()
+                .map(|s| {
+                    s.parse::<i64>()
+                        .map_err(|e| AocError::parse(format!("bad number '{}': {}", s, e)))
+                })
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> Result<i64, AocError> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(input: &Self::Input) -> Result<i64, AocError> {
+            input
+                .iter()
+                .max()
+                .copied()
+                .ok_or_else(|| AocError::infeasible("no numbers"))
+        }
+    }
+
+    struct PartOneOnly;
+
+    impl Solution for PartOneOnly {
+        const DAY: u8 = 0;
+        type Input = ();
+        type Part1 = i64;
+        type Part2 = i64;
+
+        fn parse(_input: &str) -> Result<Self::Input, AocError> {
+            Ok(())
+        }
+
+        fn part1(_input: &Self::Input) -> Result<i64, AocError> {
+            Ok(1)
+        }
+
+        fn part2(_input: &Self::Input) -> Result<i64, AocError> {
+            Err(AocError::UnsupportedPart { part: 2 })
+        }
+    }
+
+    #[test]
+    fn test_solve_formats_both_parts() {
+        let answers = solve::<Sum>("1 2 3").unwrap();
+        assert_eq!(answers.to_json(), "{\"part1\": 6, \"part2\": 3}");
+        assert!(answers.is_ok());
+    }
+
+    #[test]
+    fn test_failed_part_is_null() {
+        let answers = solve::<Sum>("").unwrap();
+        assert_eq!(
+            answers.to_json(),
+            "{\"part1\": 0, \"part2\": null, \
+             \"error\": {\"kind\": \"infeasible\", \"message\": \"no numbers\"}}"
+        );
+        assert!(!answers.is_ok());
+        assert_eq!(answers.exit_code(), 5);
+    }
+
+    #[test]
+    fn test_unsupported_part_is_not_a_failure() {
+        let answers = solve::<PartOneOnly>("").unwrap();
+        assert_eq!(answers.to_json(), "{\"part1\": 1, \"part2\": null}");
+        assert!(answers.is_ok());
+        assert_eq!(answers.exit_code(), 0);
+    }
+
+    #[test]
+    fn test_solve_part() {
+        assert_eq!(solve_part::<Sum>("1 2 3", 1), Ok(Answer::Integer(6)));
+        assert_eq!(solve_part::<Sum>("1 2 3", 2), Ok(Answer::Integer(3)));
+        assert_eq!(
+            solve_part::<Sum>("1 2 3", 3),
+            Err(AocError::UnsupportedPart { part: 3 })
+        );
+        assert!(matches!(
+            solve_part::<Sum>("1 x", 1),
+            Err(AocError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = solve::<Sum>("1 x").unwrap_err();
+        assert_eq!(error.exit_code(), 4);
+    }
+
+    #[test]
+    fn test_additional_info_escapes_newlines() {
+        let answers = Answers {
+            part1: Ok(Answer::Integer(1)),
+            part2: Ok(Answer::Integer(2)),
+            additional_info: Some(serde_json::json!({"grid": "ab\ncd"})),
+            timings: None,
+        };
+        assert_eq!(
+            answers.to_json(),
+            "{\"part1\": 1, \"part2\": 2, \"additional-info\": {\"grid\": \"ab\\ncd\"}}"
+        );
+    }
+
+    #[test]
+    fn test_run_options_from_args() {
+        let parse = |args: &[&str]| {
+            RunOptions::from_args(&args.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+        };
+        assert_eq!(
+            parse(&[]),
+            Ok(RunOptions {
+                source: InputSource::Stdin,
+                bench_runs: None,
+            })
+        );
+        assert_eq!(
+            parse(&["--bench", "--sample"]),
+            Ok(RunOptions {
+                source: InputSource::Challenge { sample: true },
+                bench_runs: Some(DEFAULT_BENCH_RUNS),
+            })
+        );
+        assert_eq!(
+            parse(&["--sample", "--bench", "3"]).unwrap().bench_runs,
+            Some(3)
+        );
+        assert!(parse(&["--bench", "0"]).is_err());
+        assert!(parse(&["--bench", "3", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_take_day_specific_flags() {
+        let mut args: Vec<String> = ["--lenient", "--sample", "--out", "a.csv"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!(take_flag(&mut args, "--lenient"));
+        assert!(!take_flag(&mut args, "--lenient"));
+        assert_eq!(
+            take_value(&mut args, "--out"),
+            Ok(Some("a.csv".to_string()))
+        );
+        assert_eq!(take_value(&mut args, "--out"), Ok(None));
+        assert_eq!(args, vec!["--sample".to_string()]);
+
+        let mut missing = vec!["--out".to_string()];
+        assert!(take_value(&mut missing, "--out").is_err());
+    }
+}
@@ -0,0 +1,184 @@
+//! Expected answers for the sample inputs, read from
+//! `challenges/dayNN/input-sample-expected-output.txt`.
+//!
+//! The file holds either a JSON object such as `{"part1": 13, "part2": 43}`
+//! or, when only part 1 is known, a bare value such as `3`. A `null` (or
+//! missing) part has no expected answer and is not checked.
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use crate::runner::Answers;
+use crate::{Answer, challenges_dir};
+
+/// The expected answer for each part, `None` when unknown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Expected {
+    /// Parse the contents of an expected-output file.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let value: Value =
+            serde_json::from_str(text.trim()).map_err(|e| format!("invalid JSON: {}", e))?;
+        match value {
+            Value::Object(object) => Ok(Expected {
+                part1: expected_answer(object.get("part1"))?,
+                part2: expected_answer(object.get("part2"))?,
+            }),
+            scalar => Ok(Expected {
+                part1: expected_answer(Some(&scalar))?,
+                part2: None,
+            }),
+        }
+    }
+
+    /// Describe every part whose answer differs from the expected one.
+    ///
+    /// A part the solver does not support is not a mismatch; a part that
+    /// failed is, when an answer is expected for it.
+    pub fn mismatches(&self, answers: &Answers) -> Vec<String> {
+        let parts = [
+            (1, &self.part1, &answers.part1),
+            (2, &self.part2, &answers.part2),
+        ];
+        parts
+            .into_iter()
+            .filter_map(|(part, expected, actual)| {
+                let expected = expected.as_ref()?;
+                match actual {
+                    Ok(answer) if answer == expected => None,
+                    Ok(answer) => Some(format!(
+                        "part {}: expected {}, got {}",
+                        part, expected, answer
+                    )),
+                    Err(e) if !e.is_failure() => None,
+                    Err(e) => Some(format!(
+                        "part {}: expected {}, got error: {}",
+                        part, expected, e
+                    )),
+                }
+            })
+            .collect()
+    }
+}
+
+fn expected_answer(value: Option<&Value>) -> Result<Option<Answer>, String> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(Answer::from(s.as_str()))),
+        Some(Value::Number(n)) => n
+            .as_i64()
+            .map(Answer::from)
+            .or_else(|| n.as_u64().map(Answer::from))
+            .map(Some)
+            .ok_or_else(|| format!("unsupported answer {}", n)),
+        Some(other) => Err(format!("unsupported answer {}", other)),
+    }
+}
+
+/// Path of a day's expected sample output.
+pub fn expected_output_path(day: u8) -> PathBuf {
+    challenges_dir()
+        .join(format!("day{:02}", day))
+        .join("input-sample-expected-output.txt")
+}
+
+/// Read and parse a day's expected sample output.
+pub fn expected_output(day: u8) -> Result<Expected, String> {
+    let path = expected_output_path(day);
+    let text = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Expected::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Every day under `challenges/` that has both a sample input and an
+/// expected output, in order.
+pub fn sample_days() -> Vec<u8> {
+    let Ok(entries) = fs::read_dir(challenges_dir()) else {
+        return Vec::new();
+    };
+    let mut days: Vec<u8> = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name();
+            name.to_str()?.strip_prefix("day")?.parse().ok()
+        })
+        .filter(|&day| {
+            crate::input_path(day, true).is_file() && expected_output_path(day).is_file()
+        })
+        .collect();
+    days.sort_unstable();
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AocError;
+
+    #[test]
+    fn test_parse_expected() {
+        assert_eq!(
+            Expected::parse("{\"part1\": 13, \"part2\": 3121910778619}\n"),
+            Ok(Expected {
+                part1: Some(Answer::Integer(13)),
+                part2: Some(Answer::Integer(3_121_910_778_619)),
+            })
+        );
+        assert_eq!(
+            Expected::parse("3\n"),
+            Ok(Expected {
+                part1: Some(Answer::Integer(3)),
+                part2: None,
+            })
+        );
+        assert_eq!(
+            Expected::parse("{\"part1\": \"abc\", \"part2\": null}"),
+            Ok(Expected {
+                part1: Some(Answer::from("abc")),
+                part2: None,
+            })
+        );
+        assert!(Expected::parse("{\"part1\": [1]}").is_err());
+        assert!(Expected::parse("part1: 3").is_err());
+    }
+
+    #[test]
+    fn test_mismatches() {
+        let expected = Expected {
+            part1: Some(Answer::Integer(7)),
+            part2: Some(Answer::Integer(33)),
+        };
+        let answers = Answers {
+            part1: Err(AocError::UnsupportedPart { part: 1 }),
+            part2: Ok(Answer::Integer(34)),
+            additional_info: None,
+            timings: None,
+        };
+        assert_eq!(
+            expected.mismatches(&answers),
+            vec!["part 2: expected 33, got 34".to_string()]
+        );
+
+        let answers = Answers {
+            part1: Err(AocError::infeasible("missing `you` node")),
+            part2: Ok(Answer::Integer(33)),
+            ..answers
+        };
+        assert_eq!(
+            expected.mismatches(&answers),
+            vec!["part 1: expected 7, got error: no solution: missing `you` node".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_sample_days_are_discovered() {
+        let days = sample_days();
+        assert!(days.contains(&1));
+        assert!(days.windows(2).all(|w| w[0] < w[1]));
+        assert!(expected_output(1).is_ok());
+    }
+}
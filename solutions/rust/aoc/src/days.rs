@@ -0,0 +1,124 @@
+use aoc_core::{Answer, Answers, AocError, Solution};
+
+/// A day's solver, type-erased so every day can be dispatched from one table.
+pub struct Day {
+    pub number: u8,
+    pub solve: fn(&str) -> Result<Answers, AocError>,
+    pub solve_part: fn(&str, u8) -> Result<Answer, AocError>,
+    pub bench: fn(&str, usize) -> Result<Answers, AocError>,
+}
+
+const fn day<S: Solution>() -> Day {
+    Day {
+        number: S::DAY,
+        solve: aoc_core::solve::<S>,
+        solve_part: aoc_core::solve_part::<S>,
+        bench: aoc_core::bench::<S>,
+    }
+}
+
+/// Every implemented day, in order.
+pub const DAYS: &[Day] = &[
+    day::<day01::Day01>(),
+    day::<day02::Day02>(),
+    day::<day03::Day03>(),
+    day::<day04::Day04>(),
+    day::<day05::Day05>(),
+    day::<day06::Day06>(),
+    day::<day07::Day07>(),
+    day::<day08::Day08>(),
+    day::<day09::Day09>(),
+    day::<day10::Day10>(),
+    day::<day11::Day11>(),
+    day::<day12::Day12>(),
+];
+
+/// Look up the solver for `number`.
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::samples;
+
+    /// Sample parts that cannot match their expected output, and why.
+    const KNOWN_SAMPLE_GAPS: &[(u8, u8, &str)] = &[(
+        11,
+        1,
+        "input-sample.txt is the part 2 sample, which has no `you` node",
+    )];
+
+    /// Days whose sample takes over a minute; run with `--ignored`.
+    const SLOW_SAMPLES: &[u8] = &[12];
+
+    /// Solve each day's sample in-process and compare it with
+    /// `input-sample-expected-output.txt`. Days without a Rust solver are skipped.
+    fn check_samples(days: impl IntoIterator<Item = u8>) {
+        let mut failures = Vec::new();
+        for number in days {
+            let Some(day) = find(number) else {
+                continue;
+            };
+            let input = std::fs::read_to_string(aoc_core::input_path(number, true)).unwrap();
+            let mut expected = samples::expected_output(number).unwrap();
+            for &(gap_day, part, _) in KNOWN_SAMPLE_GAPS {
+                if gap_day == number {
+                    match part {
+                        1 => expected.part1 = None,
+                        _ => expected.part2 = None,
+                    }
+                }
+            }
+            match (day.solve)(&input) {
+                Ok(answers) => failures.extend(
+                    expected
+                        .mismatches(&answers)
+                        .into_iter()
+                        .map(|m| format!("day {:02} {}", number, m)),
+                ),
+                Err(e) => failures.push(format!("day {:02}: {}", number, e)),
+            }
+        }
+        assert!(
+            failures.is_empty(),
+            "sample answers differ from the expected output:\n{}",
+            failures.join("\n")
+        );
+    }
+
+    #[test]
+    fn test_samples_match_expected_output() {
+        let days = samples::sample_days()
+            .into_iter()
+            .filter(|day| !SLOW_SAMPLES.contains(day));
+        check_samples(days);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_slow_samples_match_expected_output() {
+        check_samples(SLOW_SAMPLES.iter().copied());
+    }
+
+    #[test]
+    fn test_days_are_in_order() {
+        let numbers: Vec<u8> = DAYS.iter().map(|day| day.number).collect();
+        assert_eq!(numbers, (1..=12).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(7).map(|day| day.number), Some(7));
+        assert!(find(13).is_none());
+    }
+
+    #[test]
+    fn test_dispatch_day01_sample() {
+        let input = std::fs::read_to_string(aoc_core::input_path(1, true)).unwrap();
+        let day = find(1).unwrap();
+        assert_eq!((day.solve_part)(&input, 1), Ok(Answer::Integer(3)));
+        assert_eq!((day.solve)(&input).unwrap().part1, Ok(Answer::Integer(3)));
+    }
+}
@@ -0,0 +1,429 @@
+//! Single entry point that runs any day's solver in-process.
+//!
+//! ```text
+//! aoc run --day 7 [--part 2 | --bench [N]] [--input challenges/day07/input.txt | --input - | --sample]
+//! aoc run --all [--sample]
+//! ```
+//!
+//! Without `--input`, a day reads `challenges/dayNN/input.txt` (or
+//! `input-sample.txt` with `--sample`); `--input -` reads stdin. A single day
+//! prints the same JSON line as the day binaries, `--all` prints a table.
+//! `--bench [N]` solves a day N times (default 10) and adds `"timings_ms"`.
+//! `-v`/`-q` adjust the log level on stderr (see `aoc_core::logging`).
+
+mod days;
+
+use std::env;
+use std::path::PathBuf;
+use std::process;
+use std::time::Instant;
+
+use aoc_core::{Answer, AocError, InputSource, Output};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+const USAGE: &str = "\
+Usage:
+  aoc run --day <N> [--part <1|2> | --bench [RUNS]] [--input <PATH|-> | --sample]
+  aoc run --all [--sample]
+
+Add -v (repeatable) for more log output on stderr or -q for none; the
+default level comes from AOC_LOG (off, error, warn, info, debug, trace).";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+    Day {
+        day: u8,
+        part: Option<u8>,
+        input: InputSource,
+        /// `Some(runs)` with `--bench`.
+        bench: Option<usize>,
+    },
+    All {
+        sample: bool,
+    },
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    aoc_core::logging::init_from_args(&mut args);
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let code = match command {
+        Command::Day {
+            day,
+            part,
+            input,
+            bench,
+        } => run_day(day, part, bench, &input),
+        Command::All { sample } => {
+            run_all(sample);
+            0
+        }
+    };
+    if code != 0 {
+        process::exit(code);
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter().map(String::as_str).peekable();
+    match args.next() {
+        Some("run") => {}
+        Some(other) => return Err(format!("Unknown command '{}'", other)),
+        None => return Err("Missing command".to_string()),
+    }
+
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut sample = false;
+    let mut all = false;
+    let mut bench = None;
+    while let Some(arg) = args.next() {
+        match arg {
+            "--day" => day = Some(parse_number(arg, args.next(), 1..=25)?),
+            "--part" => part = Some(parse_number(arg, args.next(), 1..=2)?),
+            "--input" => match args.next() {
+                Some("-") => input = Some(InputSource::Stdin),
+                Some(path) => input = Some(InputSource::File(PathBuf::from(path))),
+                None => return Err("--input needs a path (or - for stdin)".to_string()),
+            },
+            "--sample" => sample = true,
+            "--all" => all = true,
+            "--bench" => {
+                let runs = match args.peek().and_then(|next| next.parse::<usize>().ok()) {
+                    Some(runs) => {
+                        args.next();
+                        runs
+                    }
+                    None => aoc_core::DEFAULT_BENCH_RUNS,
+                };
+                if runs == 0 {
+                    return Err("--bench needs at least one run".to_string());
+                }
+                bench = Some(runs);
+            }
+            other => return Err(format!("Unknown argument '{}'", other)),
+        }
+    }
+
+    if sample && input.is_some() {
+        return Err("--sample and --input cannot be combined".to_string());
+    }
+    if bench.is_some() && part.is_some() {
+        return Err("--bench times both parts and cannot be combined with --part".to_string());
+    }
+    match (all, day) {
+        (true, Some(_)) => Err("--all and --day cannot be combined".to_string()),
+        (true, None) if part.is_some() || input.is_some() || bench.is_some() => {
+            Err("--all only accepts --sample".to_string())
+        }
+        (true, None) => Ok(Command::All { sample }),
+        (false, Some(day)) => Ok(Command::Day {
+            day,
+            part,
+            input: input.unwrap_or(InputSource::Challenge { sample }),
+            bench,
+        }),
+        (false, None) => Err("Either --day or --all is required".to_string()),
+    }
+}
+
+fn parse_number(
+    flag: &str,
+    value: Option<&str>,
+    range: std::ops::RangeInclusive<u8>,
+) -> Result<u8, String> {
+    value
+        .and_then(|v| v.parse::<u8>().ok())
+        .filter(|n| range.contains(n))
+        .ok_or_else(|| {
+            format!(
+                "{} needs a number from {} to {}",
+                flag,
+                range.start(),
+                range.end()
+            )
+        })
+}
+
+/// Run one day (or one part of it, or a `--bench` of it) and print its JSON
+/// line. Returns the process exit code documented in `aoc_core::error`.
+fn run_day(number: u8, part: Option<u8>, bench: Option<usize>, source: &InputSource) -> i32 {
+    let Some(day) = days::find(number) else {
+        eprintln!("Day {:02} is not implemented", number);
+        return 2;
+    };
+    let input = match source.read(number) {
+        Ok(input) => input,
+        Err(e) => return report_fatal(number, e),
+    };
+
+    match part {
+        Some(part) => {
+            let result = (day.solve_part)(&input, part);
+            if let Err(e) = &result {
+                aoc_core::report_part_error(number, part, e);
+            }
+            println!("{}", part_json(part, &result));
+            result.err().map_or(0, |e| e.exit_code())
+        }
+        None => match bench.map_or_else(|| (day.solve)(&input), |runs| (day.bench)(&input, runs)) {
+            Ok(answers) => {
+                answers.report_errors(number);
+                println!("{}", answers.to_json());
+                answers.exit_code()
+            }
+            Err(e) => report_fatal(number, e),
+        },
+    }
+}
+
+/// Report an error that stopped both parts; returns its exit code.
+fn report_fatal(number: u8, error: AocError) -> i32 {
+    log::error!("Day {:02}: {}", number, error);
+    let code = error.exit_code();
+    println!("{}", Output::from_error(error).to_json());
+    code
+}
+
+/// JSON line for a single part, e.g. `{"part2": 40}`, with an `"error"`
+/// object when the part failed.
+fn part_json(part: u8, result: &Result<Answer, AocError>) -> String {
+    #[derive(Serialize)]
+    struct PartOutput<'a> {
+        #[serde(flatten)]
+        answer: Map<String, Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<&'a AocError>,
+    }
+
+    let mut answer = Map::new();
+    let value = result.as_ref().ok().cloned().unwrap_or(Answer::Null);
+    answer.insert(format!("part{}", part), json!(value));
+    let error = result.as_ref().err().filter(|e| e.is_failure());
+    aoc_core::to_json(&PartOutput { answer, error })
+}
+
+/// One line of the `--all` table.
+struct Row {
+    day: u8,
+    part1: String,
+    part2: String,
+    millis: Option<f64>,
+}
+
+/// Run every day and print a combined table. Failures are logged to stderr
+/// and shown as `error` (or `no input`) in the table; unsupported parts show `-`.
+fn run_all(sample: bool) {
+    let mut rows = Vec::new();
+    for day in days::DAYS {
+        let input = match (InputSource::Challenge { sample }).read(day.number) {
+            Ok(input) => input,
+            Err(e) => {
+                log::warn!("Day {:02}: {}", day.number, e);
+                rows.push(Row {
+                    day: day.number,
+                    part1: "no input".to_string(),
+                    part2: "no input".to_string(),
+                    millis: None,
+                });
+                continue;
+            }
+        };
+
+        let start = Instant::now();
+        let result = (day.solve)(&input);
+        let millis = Some(start.elapsed().as_secs_f64() * 1000.0);
+        let row = match result {
+            Ok(answers) => {
+                answers.report_errors(day.number);
+                Row {
+                    day: day.number,
+                    part1: table_cell(&answers.part1),
+                    part2: table_cell(&answers.part2),
+                    millis,
+                }
+            }
+            Err(e) => {
+                log::error!("Day {:02}: {}", day.number, e);
+                Row {
+                    day: day.number,
+                    part1: "error".to_string(),
+                    part2: "error".to_string(),
+                    millis,
+                }
+            }
+        };
+        rows.push(row);
+    }
+    print!("{}", format_table(&rows));
+}
+
+fn table_cell(part: &Result<Answer, AocError>) -> String {
+    match part {
+        Ok(answer) => answer.to_string(),
+        Err(AocError::UnsupportedPart { .. }) => "-".to_string(),
+        Err(_) => "error".to_string(),
+    }
+}
+
+fn format_table(rows: &[Row]) -> String {
+    let width = rows
+        .iter()
+        .flat_map(|row| [row.part1.len(), row.part2.len()])
+        .chain(["Part 1".len()])
+        .max()
+        .unwrap_or(0);
+
+    let mut table = format!(
+        "{:<3}  {:<width$}  {:<width$}  {:>10}\n",
+        "Day", "Part 1", "Part 2", "Time (ms)"
+    );
+    for row in rows {
+        let time = match row.millis {
+            Some(ms) => format!("{:.1}", ms),
+            None => "-".to_string(),
+        };
+        table.push_str(&format!(
+            "{:<3}  {:<width$}  {:<width$}  {:>10}\n",
+            format!("{:02}", row.day),
+            row.part1,
+            row.part2,
+            time
+        ));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_run_day() {
+        assert_eq!(
+            parse_args(&args(
+                "run --day 7 --part 2 --input challenges/day07/input.txt"
+            )),
+            Ok(Command::Day {
+                day: 7,
+                part: Some(2),
+                input: InputSource::File(PathBuf::from("challenges/day07/input.txt")),
+                bench: None,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run --day 3 --sample")),
+            Ok(Command::Day {
+                day: 3,
+                part: None,
+                input: InputSource::Challenge { sample: true },
+                bench: None,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run --day 3 --input -")),
+            Ok(Command::Day {
+                day: 3,
+                part: None,
+                input: InputSource::Stdin,
+                bench: None,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run --day 4 --bench 5 --sample")),
+            Ok(Command::Day {
+                day: 4,
+                part: None,
+                input: InputSource::Challenge { sample: true },
+                bench: Some(5),
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run --day 4 --bench")),
+            Ok(Command::Day {
+                day: 4,
+                part: None,
+                input: InputSource::Challenge { sample: false },
+                bench: Some(aoc_core::DEFAULT_BENCH_RUNS),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_run_all() {
+        assert_eq!(
+            parse_args(&args("run --all")),
+            Ok(Command::All { sample: false })
+        );
+        assert_eq!(
+            parse_args(&args("run --all --sample")),
+            Ok(Command::All { sample: true })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_args(&args("")).is_err());
+        assert!(parse_args(&args("build --day 1")).is_err());
+        assert!(parse_args(&args("run")).is_err());
+        assert!(parse_args(&args("run --day 0")).is_err());
+        assert!(parse_args(&args("run --day 1 --part 3")).is_err());
+        assert!(parse_args(&args("run --day 1 --input")).is_err());
+        assert!(parse_args(&args("run --day 1 --sample --input x.txt")).is_err());
+        assert!(parse_args(&args("run --all --day 1")).is_err());
+        assert!(parse_args(&args("run --all --part 1")).is_err());
+        assert!(parse_args(&args("run --all --bench")).is_err());
+        assert!(parse_args(&args("run --day 1 --part 1 --bench 3")).is_err());
+        assert!(parse_args(&args("run --day 1 --bench 0")).is_err());
+    }
+
+    #[test]
+    fn test_part_json() {
+        assert_eq!(part_json(2, &Ok(Answer::Integer(40))), "{\"part2\": 40}");
+        assert_eq!(
+            part_json(2, &Err(AocError::UnsupportedPart { part: 2 })),
+            "{\"part2\": null}"
+        );
+        assert_eq!(
+            part_json(1, &Err(AocError::timeout("slow"))),
+            "{\"part1\": null, \"error\": {\"kind\": \"timeout\", \"message\": \"slow\"}}"
+        );
+    }
+
+    #[test]
+    fn test_format_table() {
+        let rows = [
+            Row {
+                day: 1,
+                part1: "3".to_string(),
+                part2: "6".to_string(),
+                millis: Some(0.31),
+            },
+            Row {
+                day: 2,
+                part1: "no input".to_string(),
+                part2: "no input".to_string(),
+                millis: None,
+            },
+        ];
+        assert_eq!(
+            format_table(&rows),
+            "Day  Part 1    Part 2     Time (ms)\n\
+             01   3         6                0.3\n\
+             02   no input  no input           -\n"
+        );
+    }
+}
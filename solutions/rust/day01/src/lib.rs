@@ -0,0 +1,1492 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use aoc_core::{AocError, Solution};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+/// Day 1: count how often a 100-position safe dial lands on (part 1) or
+/// passes through (part 2) position 0.
+///
+/// Parsing is strict: any line that is not an instruction fails the input
+/// (see [`ParseMode`]); [`LenientDay01`] skips such lines instead.
+///
+/// Lines may be prefixed with a dial label (`A:L68`) to drive several
+/// independent dials from one instruction stream; the answers are then
+/// summed over every dial and the per-dial counts are reported under
+/// `"additional-info"`.
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Input = Vec<Instruction>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_instructions(input, ParseMode::Strict)
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<i64, AocError> {
+        Ok(DialSet::default()
+            .evaluate(instructions)?
+            .values()
+            .map(|t| t.landed)
+            .sum())
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<i64, AocError> {
+        Ok(DialSet::default()
+            .evaluate(instructions)?
+            .values()
+            .map(|t| t.passed)
+            .sum())
+    }
+
+    fn additional_info(instructions: &Self::Input) -> Option<Value> {
+        dial_info(&DialSet::default().evaluate(instructions).ok()?)
+    }
+}
+
+/// Per-dial counts for `"additional-info"`, when any instruction is labelled.
+pub fn dial_info(tallies: &BTreeMap<Option<String>, Tally>) -> Option<Value> {
+    if tallies.keys().all(Option::is_none) {
+        return None;
+    }
+    let dials: serde_json::Map<String, Value> = tallies
+        .iter()
+        .map(|(label, tally)| {
+            (
+                label.clone().unwrap_or_default(),
+                json!({ "landed": tally.landed, "passed": tally.passed }),
+            )
+        })
+        .collect();
+    Some(json!({ "dials": dials }))
+}
+
+/// [`Day01`] with [`ParseMode::Lenient`] parsing.
+pub struct LenientDay01;
+
+impl Solution for LenientDay01 {
+    const DAY: u8 = 1;
+    type Input = Vec<Instruction>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_instructions(input, ParseMode::Lenient)
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<i64, AocError> {
+        Day01::part1(instructions)
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<i64, AocError> {
+        Day01::part2(instructions)
+    }
+
+    fn additional_info(instructions: &Self::Input) -> Option<Value> {
+        Day01::additional_info(instructions)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// The instruction letter, `L` or `R`.
+    pub fn letter(&self) -> char {
+        match self {
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Rotation {
+    pub direction: Direction,
+    pub distance: i64,
+}
+
+/// A circular dial with positions `0..modulus`, turned from `start`, whose
+/// landings on and passes through `target` are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dial {
+    modulus: i64,
+    start: i64,
+    target: i64,
+}
+
+impl Dial {
+    /// The puzzle's safe dial: 100 positions, starting at 50, counting 0.
+    pub const SAFE: Dial = Dial {
+        modulus: 100,
+        start: 50,
+        target: 0,
+    };
+
+    /// Returns `None` unless `modulus` is positive and both `start` and
+    /// `target` are positions on the dial.
+    pub fn new(modulus: i64, start: i64, target: i64) -> Option<Dial> {
+        let on_dial = |position: i64| (0..modulus).contains(&position);
+        (modulus > 0 && on_dial(start) && on_dial(target)).then_some(Dial {
+            modulus,
+            start,
+            target,
+        })
+    }
+
+    pub fn modulus(&self) -> i64 {
+        self.modulus
+    }
+
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    pub fn target(&self) -> i64 {
+        self.target
+    }
+
+    /// Position after turning the dial from `position`.
+    pub fn rotate(&self, position: i64, rotation: &Rotation) -> i64 {
+        // Drop whole turns first, then widen: a position plus a part turn
+        // overflows i64 for moduli above i64::MAX / 2
+        let turn = i128::from(rotation.distance % self.modulus);
+        let new_position = match rotation.direction {
+            Direction::Left => i128::from(position) - turn,
+            Direction::Right => i128::from(position) + turn,
+        };
+        new_position.rem_euclid(i128::from(self.modulus)) as i64
+    }
+
+    /// Counts how many clicks of a rotation from `position` land on the
+    /// target, including the final one.
+    ///
+    /// The first hit is `steps` clicks away (a full turn when already on the
+    /// target) and every further full turn adds one more.
+    ///
+    /// A negative distance, which only lenient parsing lets through, turns
+    /// the dial the other way and counts each whole turn as -1, as the
+    /// original solver did.
+    pub fn count_passes(&self, position: i64, rotation: &Rotation) -> i64 {
+        if rotation.distance < 0 {
+            return rotation.distance / self.modulus;
+        }
+
+        let offset = match rotation.direction {
+            Direction::Right => self.target - position,
+            Direction::Left => position - self.target,
+        };
+        let steps = match offset.rem_euclid(self.modulus) {
+            0 => self.modulus,
+            steps => steps,
+        };
+
+        if rotation.distance < steps {
+            0
+        } else {
+            (rotation.distance - steps) / self.modulus + 1
+        }
+    }
+
+    /// Runs the rotations from the start position.
+    ///
+    /// Fails with [`AocError::Infeasible`] if either count would pass
+    /// `i64::MAX`, which a few hundred maximum-distance rotations can do.
+    pub fn evaluate<'a>(
+        &self,
+        rotations: impl IntoIterator<Item = &'a Rotation>,
+    ) -> Result<Tally, AocError> {
+        let mut position = self.start;
+        let mut tally = Tally::default();
+
+        for (index, rotation) in rotations.into_iter().enumerate() {
+            let passed = self.count_passes(position, rotation);
+            position = self.rotate(position, rotation);
+            tally
+                .add(i64::from(position == self.target), passed)
+                .ok_or_else(|| tally_overflow(index + 1))?;
+        }
+
+        Ok(tally)
+    }
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Dial::SAFE
+    }
+}
+
+/// Parses `MODULUS,START,TARGET`, e.g. `100,50,0` for [`Dial::SAFE`].
+impl FromStr for Dial {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split(',').map(str::trim).collect();
+        let [modulus, start, target] = fields[..] else {
+            return Err(format!(
+                "dial '{}' needs MODULUS,START,TARGET, e.g. 100,50,0",
+                s
+            ));
+        };
+        let number = |field: &str| {
+            field
+                .parse::<i64>()
+                .map_err(|_| format!("dial '{}': invalid number '{}'", s, field))
+        };
+        Dial::new(number(modulus)?, number(start)?, number(target)?).ok_or_else(|| {
+            format!(
+                "dial '{}': the modulus must be positive with start and target in 0..modulus",
+                s
+            )
+        })
+    }
+}
+
+/// How often a dial landed on (part 1) and passed through (part 2) its target.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub landed: i64,
+    pub passed: i64,
+}
+
+impl Tally {
+    /// Adds one rotation's counts, or returns `None`, leaving the tally as
+    /// it was, if either would overflow.
+    fn add(&mut self, landed: i64, passed: i64) -> Option<()> {
+        let landed = self.landed.checked_add(landed)?;
+        let passed = self.passed.checked_add(passed)?;
+        *self = Tally { landed, passed };
+        Some(())
+    }
+}
+
+fn tally_overflow(step: usize) -> AocError {
+    AocError::infeasible(format!("rotation {}: the counts overflow i64", step))
+}
+
+/// A rotation for the dial named by `dial`, or for the unlabelled dial.
+#[derive(Debug, PartialEq)]
+pub struct Instruction {
+    pub dial: Option<String>,
+    pub rotation: Rotation,
+}
+
+/// Dial settings for an instruction stream: labelled dials listed in
+/// `dials` use their own settings and every other dial uses `default`.
+#[derive(Debug, Clone, Default)]
+pub struct DialSet {
+    pub default: Dial,
+    pub dials: BTreeMap<String, Dial>,
+}
+
+impl DialSet {
+    pub fn new(default: Dial) -> Self {
+        DialSet {
+            default,
+            dials: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_dial(mut self, label: impl Into<String>, dial: Dial) -> Self {
+        self.dials.insert(label.into(), dial);
+        self
+    }
+
+    pub fn dial(&self, label: Option<&str>) -> Dial {
+        label
+            .and_then(|label| self.dials.get(label))
+            .copied()
+            .unwrap_or(self.default)
+    }
+
+    /// Tallies every dial that appears in the stream, keyed by label with the
+    /// unlabelled dial under `None`. Dials turn independently, so each one
+    /// only sees its own rotations in stream order.
+    pub fn evaluate(
+        &self,
+        instructions: &[Instruction],
+    ) -> Result<BTreeMap<Option<String>, Tally>, AocError> {
+        let mut replay = Replay::new(self);
+        for instruction in instructions {
+            replay.step(instruction)?;
+        }
+        Ok(replay.tallies())
+    }
+}
+
+/// How `parse_instructions` treats lines that are not valid instructions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Reject the input, reporting every bad line. The harness default.
+    #[default]
+    Strict,
+    /// Skip bad lines with a warning, and keep negative distances as the
+    /// original solver did; see [`Dial::count_passes`].
+    Lenient,
+}
+
+/// Why a line was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejectReason {
+    UnknownDirection(char),
+    MissingDistance,
+    InvalidDistance(String),
+    /// Only rejected in [`ParseMode::Strict`].
+    NegativeDistance(i64),
+    EmptyLabel,
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RejectReason::UnknownDirection(c) => {
+                write!(f, "unknown direction '{}' (expected L or R)", c)
+            }
+            RejectReason::MissingDistance => write!(f, "missing distance"),
+            RejectReason::InvalidDistance(s) => write!(f, "invalid distance '{}'", s),
+            RejectReason::NegativeDistance(d) => write!(f, "negative distance {}", d),
+            RejectReason::EmptyLabel => write!(f, "empty dial label"),
+        }
+    }
+}
+
+/// Parses `L68` or `R48`, allowing whitespace around the line and between
+/// the direction and the distance. Zero distances are valid; a `+` sign is
+/// allowed and a `-` sign is handled as `mode` says.
+pub fn parse_rotation_with(line: &str, mode: ParseMode) -> Result<Rotation, RejectReason> {
+    let line = line.trim();
+    let mut chars = line.chars();
+    let direction = match chars.next() {
+        Some('L') => Direction::Left,
+        Some('R') => Direction::Right,
+        Some(c) => return Err(RejectReason::UnknownDirection(c)),
+        None => return Err(RejectReason::MissingDistance),
+    };
+
+    let digits = chars.as_str().trim_start();
+    if digits.is_empty() {
+        return Err(RejectReason::MissingDistance);
+    }
+    let distance = digits
+        .parse::<i64>()
+        .map_err(|_| RejectReason::InvalidDistance(digits.to_string()))?;
+
+    if distance < 0 && mode == ParseMode::Strict {
+        return Err(RejectReason::NegativeDistance(distance));
+    }
+    Ok(Rotation {
+        direction,
+        distance,
+    })
+}
+
+/// Parses `L68` for the unlabelled dial or `A:L68` for dial `A`.
+pub fn parse_instruction_with(line: &str, mode: ParseMode) -> Result<Instruction, RejectReason> {
+    let (dial, rotation) = match line.split_once(':') {
+        Some((label, rotation)) => {
+            let label = label.trim();
+            if label.is_empty() {
+                return Err(RejectReason::EmptyLabel);
+            }
+            (Some(label.to_string()), rotation)
+        }
+        None => (None, line),
+    };
+    Ok(Instruction {
+        dial,
+        rotation: parse_rotation_with(rotation, mode)?,
+    })
+}
+
+/// Parses every non-blank line. In strict mode any rejected line fails the
+/// whole input with a parse error at the first one; every rejected line is
+/// logged with its 1-based line number either way.
+pub fn parse_instructions(input: &str, mode: ParseMode) -> Result<Vec<Instruction>, AocError> {
+    let mut instructions = Vec::new();
+    let mut rejections = Rejections::new(mode);
+
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse_instruction_with(line, mode) {
+            Ok(instruction) => instructions.push(instruction),
+            Err(reason) => rejections.record(index + 1, line, reason),
+        }
+    }
+
+    rejections.finish()?;
+    Ok(instructions)
+}
+
+/// Logs rejected lines as they are found, keeping only the first one and a
+/// count so that a stream of any length is checked in constant memory.
+struct Rejections {
+    mode: ParseMode,
+    first: Option<(usize, RejectReason)>,
+    count: usize,
+}
+
+impl Rejections {
+    fn new(mode: ParseMode) -> Self {
+        Rejections {
+            mode,
+            first: None,
+            count: 0,
+        }
+    }
+
+    fn record(&mut self, line_number: usize, line: &str, reason: RejectReason) {
+        match self.mode {
+            ParseMode::Strict => {
+                log::error!("line {}: {} in '{}'", line_number, reason, line.trim())
+            }
+            ParseMode::Lenient => {
+                log::warn!(
+                    "line {}: skipping '{}': {}",
+                    line_number,
+                    line.trim(),
+                    reason
+                )
+            }
+        }
+        self.count += 1;
+        self.first.get_or_insert((line_number, reason));
+    }
+
+    /// In strict mode, a parse error at the first rejected line.
+    fn finish(self) -> Result<(), AocError> {
+        match (self.mode, self.first) {
+            (ParseMode::Strict, Some((line_number, reason))) => {
+                let message = if self.count == 1 {
+                    reason.to_string()
+                } else {
+                    format!("{} ({} rejected lines)", reason, self.count)
+                };
+                Err(AocError::parse_at_line(line_number, message))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn parse_rotation(line: &str) -> Option<Rotation> {
+    parse_rotation_with(line, ParseMode::Lenient).ok()
+}
+
+/// Lenient [`parse_instruction_with`]: `None` for a line that is not an
+/// instruction.
+pub fn parse_instruction(line: &str) -> Option<Instruction> {
+    parse_instruction_with(line, ParseMode::Lenient).ok()
+}
+
+/// Position after turning the safe dial; see [`Dial::rotate`].
+pub fn rotate_dial(position: i64, rotation: &Rotation) -> i64 {
+    Dial::SAFE.rotate(position, rotation)
+}
+
+/// Counts how many times the safe dial crosses through position 0 during a
+/// rotation; see [`Dial::count_passes`].
+pub fn count_zero_crossings(position: i64, rotation: &Rotation) -> i64 {
+    Dial::SAFE.count_passes(position, rotation)
+}
+
+/// Runs the rotations on the safe dial from the starting position 50.
+/// Returns (times landed on 0, times passed through 0).
+pub fn evaluate(rotations: &[Rotation]) -> Result<(i64, i64), AocError> {
+    let tally = Dial::SAFE.evaluate(rotations)?;
+    Ok((tally.landed, tally.passed))
+}
+
+pub fn solve(input: &str) -> Result<(i64, i64), AocError> {
+    let rotations: Vec<Rotation> = input.lines().filter_map(parse_rotation).collect();
+    evaluate(&rotations)
+}
+
+/// Output format for `--trace`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// One JSON object per line.
+    Json,
+    /// A header row, then one comma-separated row per rotation.
+    Csv,
+}
+
+impl FromStr for TraceFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(TraceFormat::Json),
+            "csv" => Ok(TraceFormat::Csv),
+            other => Err(format!(
+                "unknown trace format '{}' (expected json or csv)",
+                other
+            )),
+        }
+    }
+}
+
+/// Remove `--trace [json|csv]` from the arguments, returning the requested
+/// format (JSON when none is given).
+pub fn take_trace_flag(args: &mut Vec<String>) -> Result<Option<TraceFormat>, String> {
+    let Some(index) = args.iter().position(|arg| arg == "--trace") else {
+        return Ok(None);
+    };
+    args.remove(index);
+    let format = match args.get(index).map(|next| next.parse::<TraceFormat>()) {
+        Some(Ok(format)) => {
+            args.remove(index);
+            format
+        }
+        _ => TraceFormat::Json,
+    };
+    if args.iter().any(|arg| arg == "--trace") {
+        return Err("--trace given more than once".to_string());
+    }
+    Ok(Some(format))
+}
+
+/// Remove every `--dial [LABEL:]MODULUS,START,TARGET` from the arguments,
+/// returning the dials they configure. An unlabelled `--dial` replaces the
+/// safe dial as the default; a labelled one applies to `LABEL:` instructions.
+pub fn take_dial_flags(args: &mut Vec<String>) -> Result<Option<DialSet>, String> {
+    let mut dials: Option<DialSet> = None;
+    let mut has_default = false;
+    while let Some(spec) = aoc_core::take_value(args, "--dial")? {
+        let set = dials.get_or_insert_with(DialSet::default);
+        match spec.split_once(':') {
+            Some((label, dial)) => {
+                let label = label.trim();
+                if label.is_empty() {
+                    return Err(format!("--dial '{}' has an empty label", spec));
+                }
+                if set.dials.insert(label.to_string(), dial.parse()?).is_some() {
+                    return Err(format!("--dial given more than once for dial '{}'", label));
+                }
+            }
+            None if has_default => {
+                return Err("--dial given more than once without a label".to_string());
+            }
+            None => {
+                set.default = spec.parse()?;
+                has_default = true;
+            }
+        }
+    }
+    Ok(dials)
+}
+
+/// One rotation as seen by `--trace`, with the part 1 and part 2 totals so far.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceRecord {
+    /// 1-based index into the instruction stream.
+    pub step: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dial: Option<String>,
+    pub start: i64,
+    pub direction: char,
+    pub distance: i64,
+    pub end: i64,
+    pub crossings: i64,
+    pub part1: i64,
+    pub part2: i64,
+}
+
+impl TraceRecord {
+    pub const CSV_HEADER: &'static str =
+        "step,dial,start,direction,distance,end,crossings,part1,part2";
+
+    pub fn to_csv(&self) -> String {
+        let dial = self.dial.as_deref().map(csv_field).unwrap_or_default();
+        format!(
+            "{},{},{},{},{},{},{},{},{}",
+            self.step,
+            dial,
+            self.start,
+            self.direction,
+            self.distance,
+            self.end,
+            self.crossings,
+            self.part1,
+            self.part2
+        )
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Turns every dial in an instruction stream one rotation at a time,
+/// keeping only each dial's position and tally.
+#[derive(Debug)]
+pub struct Replay<'a> {
+    dials: &'a DialSet,
+    unlabelled: Option<DialState>,
+    labelled: BTreeMap<String, DialState>,
+    steps: usize,
+    totals: Tally,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DialState {
+    position: i64,
+    tally: Tally,
+}
+
+impl<'a> Replay<'a> {
+    pub fn new(dials: &'a DialSet) -> Self {
+        Replay {
+            dials,
+            unlabelled: None,
+            labelled: BTreeMap::new(),
+            steps: 0,
+            totals: Tally::default(),
+        }
+    }
+
+    /// Applies one instruction, returning its trace record.
+    ///
+    /// Fails with [`AocError::Infeasible`], changing nothing, if a count
+    /// would pass `i64::MAX`.
+    pub fn step(&mut self, instruction: &Instruction) -> Result<TraceRecord, AocError> {
+        let label = instruction.dial.as_deref();
+        let dial = self.dials.dial(label);
+        let state = match label {
+            None => self.unlabelled,
+            Some(label) => self.labelled.get(label).copied(),
+        };
+        let DialState {
+            position: start,
+            mut tally,
+        } = state.unwrap_or(DialState {
+            position: dial.start(),
+            tally: Tally::default(),
+        });
+        let rotation = &instruction.rotation;
+
+        let crossings = dial.count_passes(start, rotation);
+        let end = dial.rotate(start, rotation);
+        let landed = i64::from(end == dial.target());
+        // Count on copies so that a failed step leaves no trace, not even a
+        // first-seen dial
+        let mut totals = self.totals;
+        if tally.add(landed, crossings).is_none() || totals.add(landed, crossings).is_none() {
+            return Err(tally_overflow(self.steps + 1));
+        }
+
+        let state = DialState {
+            position: end,
+            tally,
+        };
+        match label {
+            None => self.unlabelled = Some(state),
+            Some(label) => match self.labelled.get_mut(label) {
+                Some(existing) => *existing = state,
+                None => {
+                    self.labelled.insert(label.to_string(), state);
+                }
+            },
+        }
+        self.totals = totals;
+        self.steps += 1;
+
+        Ok(TraceRecord {
+            step: self.steps,
+            dial: instruction.dial.clone(),
+            start,
+            direction: rotation.direction.letter(),
+            distance: rotation.distance,
+            end,
+            crossings,
+            part1: self.totals.landed,
+            part2: self.totals.passed,
+        })
+    }
+
+    /// Rotations applied so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// The part 1 and part 2 answers so far, summed over every dial.
+    pub fn totals(&self) -> Tally {
+        self.totals
+    }
+
+    /// Each dial seen so far, keyed by label with the unlabelled dial under
+    /// `None`.
+    pub fn tallies(&self) -> BTreeMap<Option<String>, Tally> {
+        let unlabelled = self.unlabelled.map(|state| (None, state.tally));
+        let labelled = self
+            .labelled
+            .iter()
+            .map(|(label, state)| (Some(label.clone()), state.tally));
+        unlabelled.into_iter().chain(labelled).collect()
+    }
+}
+
+/// Replays the instructions one rotation at a time. The last record's totals
+/// are the part 1 and part 2 answers.
+pub fn trace(dials: &DialSet, instructions: &[Instruction]) -> Result<Vec<TraceRecord>, AocError> {
+    let mut replay = Replay::new(dials);
+    instructions
+        .iter()
+        .map(|instruction| replay.step(instruction))
+        .collect()
+}
+
+/// Log the running answers every this many rotations in [`evaluate_stream`].
+pub const PROGRESS_INTERVAL: usize = 1_000_000;
+
+/// Evaluates instructions read a line at a time from `reader`, in memory that
+/// does not grow with the input, calling `on_record` after each rotation.
+///
+/// Rejected lines are handled as in [`parse_instructions`]; in strict mode
+/// the error comes once the whole stream has been read, so records for the
+/// lines before it will already have been passed to `on_record`.
+pub fn evaluate_stream<'a>(
+    mut reader: impl BufRead,
+    dials: &'a DialSet,
+    mode: ParseMode,
+    mut on_record: impl FnMut(&TraceRecord),
+) -> Result<Replay<'a>, AocError> {
+    let mut replay = Replay::new(dials);
+    let mut rejections = Rejections::new(mode);
+    let mut line = String::new();
+    let mut line_number = 0;
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_number += 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse_instruction_with(&line, mode) {
+            Ok(instruction) => {
+                on_record(&replay.step(&instruction)?);
+                if replay.steps().is_multiple_of(PROGRESS_INTERVAL) {
+                    let totals = replay.totals();
+                    log::info!(
+                        "{} rotations: part1={} part2={}",
+                        replay.steps(),
+                        totals.landed,
+                        totals.passed
+                    );
+                }
+            }
+            Err(reason) => rejections.record(line_number, &line, reason),
+        }
+    }
+
+    rejections.finish()?;
+    Ok(replay)
+}
+
+/// Writes the records in `format`, one line each.
+pub fn write_trace(
+    out: &mut impl Write,
+    format: TraceFormat,
+    records: &[TraceRecord],
+) -> io::Result<()> {
+    write_trace_header(out, format)?;
+    for record in records {
+        write_trace_record(out, format, record)?;
+    }
+    Ok(())
+}
+
+/// The CSV header row; JSON has none.
+pub fn write_trace_header(out: &mut impl Write, format: TraceFormat) -> io::Result<()> {
+    match format {
+        TraceFormat::Json => Ok(()),
+        TraceFormat::Csv => writeln!(out, "{}", TraceRecord::CSV_HEADER),
+    }
+}
+
+pub fn write_trace_record(
+    out: &mut impl Write,
+    format: TraceFormat,
+    record: &TraceRecord,
+) -> io::Result<()> {
+    match format {
+        TraceFormat::Json => writeln!(out, "{}", aoc_core::to_json(record)),
+        TraceFormat::Csv => writeln!(out, "{}", record.to_csv()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Existing Part 1 tests
+
+    #[test]
+    fn test_parse_left_rotation() {
+        let rotation = parse_rotation("L68").unwrap();
+        assert_eq!(rotation.direction, Direction::Left);
+        assert_eq!(rotation.distance, 68);
+    }
+
+    #[test]
+    fn test_parse_right_rotation() {
+        let rotation = parse_rotation("R48").unwrap();
+        assert_eq!(rotation.direction, Direction::Right);
+        assert_eq!(rotation.distance, 48);
+    }
+
+    #[test]
+    fn test_left_rotation_with_wraparound() {
+        let rotation = Rotation {
+            direction: Direction::Left,
+            distance: 10,
+        };
+        let result = rotate_dial(5, &rotation);
+        assert_eq!(result, 95);
+    }
+
+    #[test]
+    fn test_right_rotation_with_wraparound() {
+        let rotation = Rotation {
+            direction: Direction::Right,
+            distance: 10,
+        };
+        let result = rotate_dial(95, &rotation);
+        assert_eq!(result, 5);
+    }
+
+    #[test]
+    fn test_rotation_lands_on_zero() {
+        let rotation = Rotation {
+            direction: Direction::Right,
+            distance: 48,
+        };
+        let result = rotate_dial(52, &rotation);
+        assert_eq!(result, 0);
+    }
+
+    #[test]
+    fn test_sample_input() {
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+        let (part1, part2) = solve(input).unwrap();
+        assert_eq!(part1, 3);
+        assert_eq!(part2, 6);
+    }
+
+    // New Part 2 tests for count_zero_crossings
+
+    #[test]
+    fn test_right_crossing_once() {
+        let rotation = Rotation {
+            direction: Direction::Right,
+            distance: 10,
+        };
+        let crossings = count_zero_crossings(95, &rotation);
+        assert_eq!(crossings, 1);
+    }
+
+    #[test]
+    fn test_left_crossing_once() {
+        let rotation = Rotation {
+            direction: Direction::Left,
+            distance: 10,
+        };
+        let crossings = count_zero_crossings(5, &rotation);
+        assert_eq!(crossings, 1);
+    }
+
+    #[test]
+    fn test_large_rotation_multiple_crossings() {
+        let rotation = Rotation {
+            direction: Direction::Right,
+            distance: 1000,
+        };
+        let crossings = count_zero_crossings(50, &rotation);
+        assert_eq!(crossings, 10);
+    }
+
+    #[test]
+    fn test_exact_multiple_of_100() {
+        let rotation = Rotation {
+            direction: Direction::Right,
+            distance: 100,
+        };
+        let crossings = count_zero_crossings(0, &rotation);
+        assert_eq!(crossings, 1);
+    }
+
+    #[test]
+    fn test_no_crossing() {
+        let rotation = Rotation {
+            direction: Direction::Right,
+            distance: 5,
+        };
+        let crossings = count_zero_crossings(50, &rotation);
+        assert_eq!(crossings, 0);
+    }
+
+    #[test]
+    fn test_starting_at_zero_right() {
+        let rotation = Rotation {
+            direction: Direction::Right,
+            distance: 10,
+        };
+        let crossings = count_zero_crossings(0, &rotation);
+        assert_eq!(crossings, 0);
+    }
+
+    #[test]
+    fn test_starting_at_zero_left() {
+        let rotation = Rotation {
+            direction: Direction::Left,
+            distance: 10,
+        };
+        let crossings = count_zero_crossings(0, &rotation);
+        assert_eq!(crossings, 0);
+    }
+
+    #[test]
+    fn test_ending_at_zero_right() {
+        let rotation = Rotation {
+            direction: Direction::Right,
+            distance: 10,
+        };
+        let crossings = count_zero_crossings(90, &rotation);
+        assert_eq!(crossings, 1);
+    }
+
+    #[test]
+    fn test_dial_new_rejects_positions_off_the_dial() {
+        assert!(Dial::new(10, 0, 9).is_some());
+        assert_eq!(Dial::new(0, 0, 0), None);
+        assert_eq!(Dial::new(10, 10, 0), None);
+        assert_eq!(Dial::new(10, 0, -1), None);
+    }
+
+    #[test]
+    fn test_dial_with_modulus_near_i64_max() {
+        let right = |distance| Rotation {
+            direction: Direction::Right,
+            distance,
+        };
+        let left = |distance| Rotation {
+            direction: Direction::Left,
+            distance,
+        };
+        let dial = Dial::new(i64::MAX, i64::MAX - 1, 0).unwrap();
+
+        // Wraps past 0 rather than overflowing i64
+        assert_eq!(dial.rotate(i64::MAX - 1, &right(5)), 4);
+        assert_eq!(dial.count_passes(i64::MAX - 1, &right(5)), 1);
+        assert_eq!(dial.rotate(4, &left(10)), i64::MAX - 6);
+        assert_eq!(dial.count_passes(4, &left(10)), 1);
+        assert_eq!(dial.rotate(i64::MAX - 6, &right(i64::MAX)), i64::MAX - 6);
+
+        // R6 lands on 0 and a full turn left lands there again
+        let tally = dial
+            .evaluate(&[right(5), left(10), right(6), left(i64::MAX)])
+            .unwrap();
+        assert_eq!(
+            tally,
+            Tally {
+                landed: 2,
+                passed: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_take_dial_flags() {
+        let mut args: Vec<String> = ["--dial", "10,0,0", "-v", "--dial", "A: 12, 3, 4"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let dials = take_dial_flags(&mut args).unwrap().unwrap();
+        assert_eq!(args, vec!["-v"]);
+        assert_eq!(dials.dial(None), Dial::new(10, 0, 0).unwrap());
+        assert_eq!(dials.dial(Some("A")), Dial::new(12, 3, 4).unwrap());
+        assert_eq!(dials.dial(Some("B")), Dial::new(10, 0, 0).unwrap());
+
+        // Only labelled dials keep the safe dial as the default
+        let mut args = vec!["--dial".to_string(), "B:5,0,4".to_string()];
+        assert_eq!(
+            take_dial_flags(&mut args).unwrap().unwrap().dial(None),
+            Dial::SAFE
+        );
+        assert!(matches!(take_dial_flags(&mut Vec::new()), Ok(None)));
+
+        for bad in [
+            vec!["--dial", "10,0"],
+            vec!["--dial", "10,x,0"],
+            vec!["--dial", "10,10,0"],
+            vec!["--dial", "0,0,0"],
+            vec!["--dial", ":10,0,0"],
+            vec!["--dial", "10,0,0", "--dial", "20,0,0"],
+            vec!["--dial", "A:10,0,0", "--dial", "A:20,0,0"],
+            vec!["--dial"],
+        ] {
+            let mut args: Vec<String> = bad.iter().map(|arg| arg.to_string()).collect();
+            assert!(take_dial_flags(&mut args).is_err(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_small_dial_with_custom_target() {
+        // 10 positions from 3, counting 7: R4 lands on 7, L12 passes 7 once
+        // (a full turn) and ends on 5, R12 passes it twice and ends on 7
+        let dial = Dial::new(10, 3, 7).unwrap();
+        let rotations: Vec<Rotation> = ["R4", "L12", "R12"]
+            .iter()
+            .filter_map(|line| parse_rotation(line))
+            .collect();
+        assert_eq!(
+            dial.evaluate(&rotations).unwrap(),
+            Tally {
+                landed: 2,
+                passed: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_labels() {
+        let labelled = parse_instruction("A:L68").unwrap();
+        assert_eq!(labelled.dial.as_deref(), Some("A"));
+        assert_eq!(labelled.rotation, parse_rotation("L68").unwrap());
+
+        let unlabelled = parse_instruction("R48").unwrap();
+        assert_eq!(unlabelled.dial, None);
+
+        assert_eq!(parse_instruction(":L68"), None);
+        assert_eq!(parse_instruction("A:X68"), None);
+    }
+
+    #[test]
+    fn test_interleaved_dials_match_separate_runs() {
+        let sample = [
+            "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82",
+        ];
+        let input: String = sample
+            .iter()
+            .flat_map(|line| [format!("A:{line}\n"), format!("B:{line}\n")])
+            .collect();
+        let instructions = Day01::parse(&input).unwrap();
+
+        assert_eq!(Day01::part1(&instructions).unwrap(), 6);
+        assert_eq!(Day01::part2(&instructions).unwrap(), 12);
+        assert_eq!(
+            Day01::additional_info(&instructions),
+            Some(json!({ "dials": {
+                "A": { "landed": 3, "passed": 6 },
+                "B": { "landed": 3, "passed": 6 },
+            } }))
+        );
+    }
+
+    #[test]
+    fn test_dial_set_uses_per_label_settings() {
+        let dials = DialSet::new(Dial::SAFE).with_dial("small", Dial::new(10, 0, 0).unwrap());
+        let instructions: Vec<Instruction> = ["small:R25", "R50", "other:R50"]
+            .iter()
+            .filter_map(|line| parse_instruction(line))
+            .collect();
+        let tallies = dials.evaluate(&instructions).unwrap();
+
+        assert_eq!(
+            tallies[&Some("small".to_string())],
+            Tally {
+                landed: 0,
+                passed: 2
+            }
+        );
+        assert_eq!(
+            tallies[&None],
+            Tally {
+                landed: 1,
+                passed: 1
+            }
+        );
+        assert_eq!(
+            tallies[&Some("other".to_string())],
+            Tally {
+                landed: 1,
+                passed: 1
+            }
+        );
+        assert_eq!(
+            Day01::additional_info(&Day01::parse("L68\nR48").unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_rotation_whitespace_and_signs() {
+        let right = |distance| Rotation {
+            direction: Direction::Right,
+            distance,
+        };
+        for line in ["R48", "  R48\t", "R 48", "R+48", "R\t+48 "] {
+            assert_eq!(
+                parse_rotation_with(line, ParseMode::Strict),
+                Ok(right(48)),
+                "{line:?}"
+            );
+        }
+        assert_eq!(parse_rotation_with("R0", ParseMode::Strict), Ok(right(0)));
+        assert_eq!(
+            parse_rotation_with("L-5", ParseMode::Lenient),
+            Ok(Rotation {
+                direction: Direction::Left,
+                distance: -5
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_rotation_reject_reasons() {
+        let strict = |line| parse_rotation_with(line, ParseMode::Strict);
+        assert_eq!(strict("X12"), Err(RejectReason::UnknownDirection('X')));
+        assert_eq!(strict("l12"), Err(RejectReason::UnknownDirection('l')));
+        assert_eq!(strict("L"), Err(RejectReason::MissingDistance));
+        assert_eq!(
+            strict("L1 2"),
+            Err(RejectReason::InvalidDistance("1 2".to_string()))
+        );
+        assert_eq!(
+            strict("R99999999999999999999"),
+            Err(RejectReason::InvalidDistance(
+                "99999999999999999999".to_string()
+            ))
+        );
+        assert_eq!(strict("L-5"), Err(RejectReason::NegativeDistance(-5)));
+        assert_eq!(
+            parse_instruction_with(" :L5", ParseMode::Strict),
+            Err(RejectReason::EmptyLabel)
+        );
+    }
+
+    #[test]
+    fn test_strict_parse_reports_first_rejected_line() {
+        let input = "L68\n\nX12\nR48\nL-5\n";
+        assert_eq!(
+            parse_instructions(input, ParseMode::Strict),
+            Err(AocError::parse_at_line(
+                3,
+                "unknown direction 'X' (expected L or R) (2 rejected lines)"
+            ))
+        );
+        assert_eq!(
+            Day01::parse("L68\nL-5"),
+            Err(AocError::parse_at_line(2, "negative distance -5"))
+        );
+    }
+
+    #[test]
+    fn test_lenient_parse_skips_rejected_lines() {
+        let input = "L68\n\nX12\nR48\nL-5\n";
+        let instructions = parse_instructions(input, ParseMode::Lenient).unwrap();
+        let distances: Vec<i64> = instructions.iter().map(|i| i.rotation.distance).collect();
+        assert_eq!(distances, vec![68, 48, -5]);
+        assert_eq!(LenientDay01::parse(input).unwrap(), instructions);
+    }
+
+    #[test]
+    fn test_lenient_negative_distances_match_the_original_solver() {
+        // From 50: L-5 turns right to 55; R-160 turns left to 95 and counts
+        // -1 for its whole turn; L-5 wraps right to 0 and lands there
+        let input = "L-5\nR-160\nL-5\n";
+        let instructions = LenientDay01::parse(input).unwrap();
+        let ends: Vec<(i64, i64)> = trace(&DialSet::default(), &instructions)
+            .unwrap()
+            .iter()
+            .map(|r| (r.end, r.crossings))
+            .collect();
+        assert_eq!(ends, vec![(55, 0), (95, -1), (0, 0)]);
+        assert_eq!(LenientDay01::part1(&instructions), Ok(1));
+        assert_eq!(LenientDay01::part2(&instructions), Ok(-1));
+        assert_eq!(solve(input), Ok((1, -1)));
+    }
+
+    #[test]
+    fn test_evaluate_stream_matches_batch() {
+        let input = "L68\nL30\nR48\nL5\nR60\n\nL55\nL1\nL99\nR14\nL82\nA:R50\n";
+        let dials = DialSet::default();
+        let mut records = Vec::new();
+        let replay = evaluate_stream(input.as_bytes(), &dials, ParseMode::Strict, |record| {
+            records.push(record.clone())
+        })
+        .unwrap();
+
+        let instructions = Day01::parse(input).unwrap();
+        assert_eq!(records, trace(&dials, &instructions).unwrap());
+        assert_eq!(replay.steps(), 11);
+        assert_eq!(replay.tallies(), dials.evaluate(&instructions).unwrap());
+        assert_eq!(
+            replay.totals(),
+            Tally {
+                landed: Day01::part1(&instructions).unwrap(),
+                passed: Day01::part2(&instructions).unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_failed_step_leaves_replay_unchanged() {
+        let max = |dial: Option<&str>| Instruction {
+            dial: dial.map(str::to_string),
+            rotation: Rotation {
+                direction: Direction::Right,
+                distance: i64::MAX,
+            },
+        };
+        let dials = DialSet::default();
+        let mut replay = Replay::new(&dials);
+        while replay.step(&max(None)).is_ok() {}
+        let (steps, totals, tallies) = (replay.steps(), replay.totals(), replay.tallies());
+        assert_eq!(steps, 100);
+
+        // Dial A has never turned, so only the totals overflow
+        assert!(matches!(
+            replay.step(&max(Some("A"))),
+            Err(AocError::Infeasible { .. })
+        ));
+        assert_eq!(replay.steps(), steps);
+        assert_eq!(replay.totals(), totals);
+        assert_eq!(replay.tallies(), tallies);
+        assert!(!replay.tallies().contains_key(&Some("A".to_string())));
+    }
+
+    #[test]
+    fn test_evaluate_stream_rejects_by_line_number() {
+        let input = "L68\n\nX12\nR48\n";
+        let dials = DialSet::default();
+        let strict = evaluate_stream(input.as_bytes(), &dials, ParseMode::Strict, |_| {});
+        assert_eq!(
+            strict.unwrap_err(),
+            AocError::parse_at_line(3, "unknown direction 'X' (expected L or R)")
+        );
+
+        let lenient = evaluate_stream(input.as_bytes(), &dials, ParseMode::Lenient, |_| {});
+        assert_eq!(lenient.unwrap().steps(), 2);
+    }
+
+    #[test]
+    fn test_counts_overflow_is_infeasible() {
+        // Each rotation passes zero about i64::MAX / 100 times, so the
+        // part 2 count overflows a little past the hundredth line
+        let input = "R9223372036854775807\n".repeat(200);
+        let dials = DialSet::default();
+        let instructions = Day01::parse(&input).unwrap();
+        let expected = AocError::infeasible("rotation 101: the counts overflow i64");
+
+        assert_eq!(Day01::part2(&instructions).unwrap_err(), expected);
+        assert_eq!(Day01::part1(&instructions).unwrap_err(), expected);
+        assert_eq!(Day01::additional_info(&instructions), None);
+
+        let mut steps = 0;
+        let stream = evaluate_stream(input.as_bytes(), &dials, ParseMode::Strict, |_| steps += 1);
+        assert_eq!(stream.unwrap_err(), expected);
+        assert_eq!(steps, 100);
+    }
+
+    #[test]
+    fn test_distances_beyond_i32() {
+        let right = |distance| Rotation {
+            direction: Direction::Right,
+            distance,
+        };
+        let max32 = i64::from(i32::MAX);
+        assert_eq!(count_zero_crossings(50, &right(max32)), 21_474_836);
+        assert_eq!(rotate_dial(50, &right(max32)), 97);
+
+        assert_eq!(count_zero_crossings(0, &right(i64::MAX)), i64::MAX / 100);
+        assert_eq!(rotate_dial(99, &right(i64::MAX)), 6);
+        assert_eq!(
+            parse_rotation("L9223372036854775807").map(|r| r.distance),
+            Some(i64::MAX)
+        );
+    }
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_take_trace_flag() {
+        let mut plain = args(&["--sample"]);
+        assert_eq!(take_trace_flag(&mut plain), Ok(None));
+        assert_eq!(plain, args(&["--sample"]));
+
+        let mut bare = args(&["--trace", "--sample"]);
+        assert_eq!(take_trace_flag(&mut bare), Ok(Some(TraceFormat::Json)));
+        assert_eq!(bare, args(&["--sample"]));
+
+        let mut csv = args(&["--input", "-", "--trace", "csv"]);
+        assert_eq!(take_trace_flag(&mut csv), Ok(Some(TraceFormat::Csv)));
+        assert_eq!(csv, args(&["--input", "-"]));
+
+        assert!(take_trace_flag(&mut args(&["--trace", "--trace"])).is_err());
+    }
+
+    #[test]
+    fn test_trace_totals_match_answers() {
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+        let instructions = Day01::parse(input).unwrap();
+        let records = trace(&DialSet::default(), &instructions).unwrap();
+
+        assert_eq!(records.len(), 10);
+        assert_eq!(
+            records[0],
+            TraceRecord {
+                step: 1,
+                dial: None,
+                start: 50,
+                direction: 'L',
+                distance: 68,
+                end: 82,
+                crossings: 1,
+                part1: 0,
+                part2: 1,
+            }
+        );
+        let last = records.last().unwrap();
+        assert_eq!((last.part1, last.part2), solve(input).unwrap());
+    }
+
+    #[test]
+    fn test_trace_follows_each_dial() {
+        let instructions = Day01::parse("A:R50\nB:L10\nA:R10").unwrap();
+        let records = trace(&DialSet::default(), &instructions).unwrap();
+        let ends: Vec<(Option<&str>, i64, i64)> = records
+            .iter()
+            .map(|r| (r.dial.as_deref(), r.start, r.end))
+            .collect();
+        assert_eq!(
+            ends,
+            vec![(Some("A"), 50, 0), (Some("B"), 50, 40), (Some("A"), 0, 10)]
+        );
+        assert_eq!((records[2].part1, records[2].part2), (1, 1));
+    }
+
+    #[test]
+    fn test_write_trace_formats() {
+        let instructions = Day01::parse("L68\nx,y:R18").unwrap();
+        let records = trace(&DialSet::default(), &instructions).unwrap();
+
+        let mut json = Vec::new();
+        write_trace(&mut json, TraceFormat::Json, &records).unwrap();
+        assert_eq!(
+            String::from_utf8(json).unwrap().lines().next(),
+            Some(
+                "{\"step\": 1, \"start\": 50, \"direction\": \"L\", \"distance\": 68, \
+                 \"end\": 82, \"crossings\": 1, \"part1\": 0, \"part2\": 1}"
+            )
+        );
+
+        let mut csv = Vec::new();
+        write_trace(&mut csv, TraceFormat::Csv, &records).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "step,dial,start,direction,distance,end,crossings,part1,part2\n\
+             1,,50,L,68,82,1,0,1\n\
+             2,\"x,y\",50,R,18,68,0,0,1\n"
+        );
+    }
+
+    // Property tests: the closed-form dial arithmetic against a click-by-click simulation
+
+    use proptest::prelude::*;
+
+    /// Turns the dial one click at a time, returning the final position and
+    /// how many clicks landed on the target.
+    fn simulate_dial(dial: Dial, position: i64, rotation: &Rotation) -> (i64, i64) {
+        let step = match rotation.direction {
+            Direction::Left => dial.modulus() - 1,
+            Direction::Right => 1,
+        };
+        let mut position = position;
+        let mut hits = 0;
+        for _ in 0..rotation.distance {
+            position = (position + step) % dial.modulus();
+            if position == dial.target() {
+                hits += 1;
+            }
+        }
+        (position, hits)
+    }
+
+    fn simulate(position: i64, rotation: &Rotation) -> (i64, i64) {
+        simulate_dial(Dial::SAFE, position, rotation)
+    }
+
+    fn dial() -> impl Strategy<Value = Dial> {
+        (1..50i64)
+            .prop_flat_map(|modulus| (Just(modulus), 0..modulus, 0..modulus))
+            .prop_map(|(modulus, start, target)| Dial::new(modulus, start, target).unwrap())
+    }
+
+    fn direction() -> impl Strategy<Value = Direction> {
+        any::<bool>().prop_map(|left| {
+            if left {
+                Direction::Left
+            } else {
+                Direction::Right
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_rotate_dial_matches_simulation(
+            position in 0..100i64,
+            direction in direction(),
+            distance in 0..1000i64,
+        ) {
+            let rotation = Rotation { direction, distance };
+            prop_assert_eq!(rotate_dial(position, &rotation), simulate(position, &rotation).0);
+        }
+
+        #[test]
+        fn prop_count_zero_crossings_matches_simulation(
+            position in 0..100i64,
+            direction in direction(),
+            distance in 0..1000i64,
+        ) {
+            let rotation = Rotation { direction, distance };
+            prop_assert_eq!(
+                count_zero_crossings(position, &rotation),
+                simulate(position, &rotation).1
+            );
+        }
+
+        #[test]
+        fn prop_any_dial_matches_simulation(
+            dial in dial(),
+            direction in direction(),
+            distance in 0..500i64,
+        ) {
+            let rotation = Rotation { direction, distance };
+            let (position, hits) = simulate_dial(dial, dial.start(), &rotation);
+            prop_assert_eq!(dial.rotate(dial.start(), &rotation), position);
+            prop_assert_eq!(dial.count_passes(dial.start(), &rotation), hits);
+        }
+    }
+}
@@ -1,263 +1,131 @@
-use std::io::{self, Read};
+use aoc_core::{Answers, Solution};
+use day01::{
+    Day01, DialSet, LenientDay01, ParseMode, Tally, TraceFormat, dial_info, evaluate_stream,
+    parse_instructions, take_dial_flags, take_trace_flag, trace, write_trace, write_trace_header,
+    write_trace_record,
+};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+const USAGE: &str =
+    "[--lenient] [--stream] [--trace [json|csv]] [--dial [LABEL:]MODULUS,START,TARGET]...";
 
 fn main() {
-    // Read input from stdin
-    let mut input = String::new();
-    io::stdin()
-        .read_to_string(&mut input)
-        .expect("Failed to read from stdin");
-
-    let (part1_result, part2_result) = solve(&input);
-
-    // Output JSON format for testing framework
-    println!("{{\"part1\": {}, \"part2\": {}}}", part1_result, part2_result);
-}
-
-#[derive(Debug, PartialEq)]
-enum Direction {
-    Left,
-    Right,
-}
-
-#[derive(Debug, PartialEq)]
-struct Rotation {
-    direction: Direction,
-    distance: i32,
-}
-
-fn parse_rotation(line: &str) -> Option<Rotation> {
-    let line = line.trim();
-    if line.is_empty() {
-        return None;
-    }
-
-    let direction = match line.chars().next()? {
-        'L' => Direction::Left,
-        'R' => Direction::Right,
-        _ => return None,
-    };
-
-    let distance = line[1..].parse::<i32>().ok()?;
-
-    Some(Rotation { direction, distance })
-}
-
-fn rotate_dial(position: i32, rotation: &Rotation) -> i32 {
-    let new_position = match rotation.direction {
-        Direction::Left => position - rotation.distance,
-        Direction::Right => position + rotation.distance,
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let trace_format =
+        take_trace_flag(&mut args).unwrap_or_else(|e| aoc_core::usage_error(Day01::DAY, &e, USAGE));
+    let mode = if aoc_core::take_flag(&mut args, "--lenient") {
+        ParseMode::Lenient
+    } else {
+        ParseMode::Strict
     };
-
-    // Handle negative modulo correctly for circular dial (0-99)
-    ((new_position % 100) + 100) % 100
-}
-
-/// Counts how many times the dial crosses through position 0 during a rotation.
-///
-/// This function calculates zero crossings by breaking the rotation into:
-/// 1. Complete circles (each crosses 0 exactly once)
-/// 2. A remainder rotation (may or may not cross 0 depending on position and direction)
-///
-/// # Arguments
-/// * `position` - Current dial position (0-99)
-/// * `rotation` - The rotation instruction (direction and distance)
-///
-/// # Returns
-/// Integer count of zero crossings (>= 0)
-fn count_zero_crossings(position: i32, rotation: &Rotation) -> i32 {
-    let amount = rotation.distance;
-
-    if amount == 0 {
-        return 0;
-    }
-
-    // Calculate complete circles and remainder
-    let complete_circles = amount / 100;
-    let remainder = amount % 100;
-
-    // Each complete circle crosses zero exactly once
-    let mut crossings = complete_circles;
-
-    match rotation.direction {
-        Direction::Right => {
-            // Distance to reach 0 going right (clockwise)
-            let distance_to_zero = 100 - position;
-            if remainder >= distance_to_zero {
-                crossings += 1;
-            }
-        }
-        Direction::Left => {
-            // Distance to reach 0 going left (counterclockwise)
-            let distance_to_zero = position;
-            if position > 0 && remainder >= distance_to_zero {
-                crossings += 1;
-            }
+    let stream = aoc_core::take_flag(&mut args, "--stream");
+    let custom_dials =
+        take_dial_flags(&mut args).unwrap_or_else(|e| aoc_core::usage_error(Day01::DAY, &e, USAGE));
+    let options = aoc_core::options_from(Day01::DAY, args, USAGE);
+
+    if options.bench_runs.is_some() && (stream || trace_format.is_some() || custom_dials.is_some())
+    {
+        aoc_core::usage_error(
+            Day01::DAY,
+            "--bench cannot be combined with --stream, --trace or --dial",
+            USAGE,
+        );
+    }
+    let dials = custom_dials.clone().unwrap_or_default();
+    if stream {
+        run_streaming(&options, &dials, mode, trace_format);
+        return;
+    }
+
+    let input = options.read_input(Day01::DAY);
+    if trace_format.is_none() && custom_dials.is_none() {
+        match mode {
+            ParseMode::Strict => aoc_core::run_with_options::<Day01>(&options, &input),
+            ParseMode::Lenient => aoc_core::run_with_options::<LenientDay01>(&options, &input),
         }
+        return;
     }
 
-    crossings
-}
-
-fn solve(input: &str) -> (i32, i32) {
-    let mut position = 50;
-    let mut part1_count = 0;
-    let mut part2_count = 0;
-
-    for line in input.lines() {
-        if let Some(rotation) = parse_rotation(line) {
-            // Calculate Part 2: count zero crossings during rotation
-            part2_count += count_zero_crossings(position, &rotation);
-
-            // Update position
-            position = rotate_dial(position, &rotation);
+    let instructions =
+        parse_instructions(&input, mode).unwrap_or_else(|e| aoc_core::fail(Day01::DAY, e));
+    let Some(format) = trace_format else {
+        let answers = dials
+            .evaluate(&instructions)
+            .map(|tallies| dial_answers(&tallies));
+        aoc_core::print_and_exit(Day01::DAY, answers);
+        return;
+    };
 
-            // Calculate Part 1: count when dial lands on 0
-            if position == 0 {
-                part1_count += 1;
-            }
-        }
+    // One record per rotation on stdout instead of the JSON answer
+    let records = trace(&dials, &instructions).unwrap_or_else(|e| aoc_core::fail(Day01::DAY, e));
+    let mut stdout = io::stdout().lock();
+    match write_trace(&mut stdout, format, &records) {
+        // The reader stopped early, e.g. `| head`
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+        Err(e) => aoc_core::fail(Day01::DAY, e.into()),
+        Ok(()) => {}
     }
-
-    (part1_count, part2_count)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    // Existing Part 1 tests
-
-    #[test]
-    fn test_parse_left_rotation() {
-        let rotation = parse_rotation("L68").unwrap();
-        assert_eq!(rotation.direction, Direction::Left);
-        assert_eq!(rotation.distance, 68);
-    }
-
-    #[test]
-    fn test_parse_right_rotation() {
-        let rotation = parse_rotation("R48").unwrap();
-        assert_eq!(rotation.direction, Direction::Right);
-        assert_eq!(rotation.distance, 48);
-    }
-
-    #[test]
-    fn test_left_rotation_with_wraparound() {
-        let rotation = Rotation {
-            direction: Direction::Left,
-            distance: 10,
-        };
-        let result = rotate_dial(5, &rotation);
-        assert_eq!(result, 95);
-    }
-
-    #[test]
-    fn test_right_rotation_with_wraparound() {
-        let rotation = Rotation {
-            direction: Direction::Right,
-            distance: 10,
-        };
-        let result = rotate_dial(95, &rotation);
-        assert_eq!(result, 5);
-    }
-
-    #[test]
-    fn test_rotation_lands_on_zero() {
-        let rotation = Rotation {
-            direction: Direction::Right,
-            distance: 48,
-        };
-        let result = rotate_dial(52, &rotation);
-        assert_eq!(result, 0);
-    }
-
-    #[test]
-    fn test_sample_input() {
-        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
-        let (part1, part2) = solve(input);
-        assert_eq!(part1, 3);
-        assert_eq!(part2, 6);
-    }
-
-    // New Part 2 tests for count_zero_crossings
-
-    #[test]
-    fn test_right_crossing_once() {
-        let rotation = Rotation {
-            direction: Direction::Right,
-            distance: 10,
-        };
-        let crossings = count_zero_crossings(95, &rotation);
-        assert_eq!(crossings, 1);
-    }
-
-    #[test]
-    fn test_left_crossing_once() {
-        let rotation = Rotation {
-            direction: Direction::Left,
-            distance: 10,
-        };
-        let crossings = count_zero_crossings(5, &rotation);
-        assert_eq!(crossings, 1);
-    }
-
-    #[test]
-    fn test_large_rotation_multiple_crossings() {
-        let rotation = Rotation {
-            direction: Direction::Right,
-            distance: 1000,
-        };
-        let crossings = count_zero_crossings(50, &rotation);
-        assert_eq!(crossings, 10);
-    }
-
-    #[test]
-    fn test_exact_multiple_of_100() {
-        let rotation = Rotation {
-            direction: Direction::Right,
-            distance: 100,
-        };
-        let crossings = count_zero_crossings(0, &rotation);
-        assert_eq!(crossings, 1);
-    }
+/// Fold over the input a line at a time without holding it in memory. With
+/// `--trace` the records are written as they are produced; otherwise the
+/// JSON answer is printed at the end.
+fn run_streaming(
+    options: &aoc_core::RunOptions,
+    dials: &DialSet,
+    mode: ParseMode,
+    trace_format: Option<TraceFormat>,
+) {
+    let reader = options
+        .source
+        .open(Day01::DAY)
+        .unwrap_or_else(|e| aoc_core::fail(Day01::DAY, e));
+    let mut out = trace_format.map(|format| (format, io::BufWriter::new(io::stdout().lock())));
+    let mut write_result = match &mut out {
+        Some((format, writer)) => write_trace_header(writer, *format),
+        None => Ok(()),
+    };
 
-    #[test]
-    fn test_no_crossing() {
-        let rotation = Rotation {
-            direction: Direction::Right,
-            distance: 5,
-        };
-        let crossings = count_zero_crossings(50, &rotation);
-        assert_eq!(crossings, 0);
+    let result = evaluate_stream(reader, dials, mode, |record| {
+        if let (Some((format, writer)), Ok(())) = (&mut out, &write_result) {
+            write_result = write_trace_record(writer, *format, record);
+        }
+    });
+    if let (Some((_, writer)), Ok(())) = (&mut out, &write_result) {
+        write_result = writer.flush();
     }
 
-    #[test]
-    fn test_starting_at_zero_right() {
-        let rotation = Rotation {
-            direction: Direction::Right,
-            distance: 10,
+    let replay = result.unwrap_or_else(|e| {
+        let e = match e {
+            aoc_core::AocError::Io { message } => aoc_core::AocError::Io {
+                message: format!("{}: {}", options.source.name(Day01::DAY), message),
+            },
+            other => other,
         };
-        let crossings = count_zero_crossings(0, &rotation);
-        assert_eq!(crossings, 0);
+        aoc_core::fail(Day01::DAY, e)
+    });
+    match write_result {
+        // The reader stopped early, e.g. `| head`
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return,
+        Err(e) => aoc_core::fail(Day01::DAY, e.into()),
+        Ok(()) if trace_format.is_some() => return,
+        Ok(()) => {}
     }
 
-    #[test]
-    fn test_starting_at_zero_left() {
-        let rotation = Rotation {
-            direction: Direction::Left,
-            distance: 10,
-        };
-        let crossings = count_zero_crossings(0, &rotation);
-        assert_eq!(crossings, 0);
-    }
+    aoc_core::print_and_exit(Day01::DAY, Ok(dial_answers(&replay.tallies())));
+}
 
-    #[test]
-    fn test_ending_at_zero_right() {
-        let rotation = Rotation {
-            direction: Direction::Right,
-            distance: 10,
-        };
-        let crossings = count_zero_crossings(90, &rotation);
-        assert_eq!(crossings, 1);
+/// The JSON answer for dial tallies: part 1 and part 2 summed over every dial.
+fn dial_answers(tallies: &BTreeMap<Option<String>, Tally>) -> Answers {
+    let totals = tallies.values().fold(Tally::default(), |sum, tally| Tally {
+        landed: sum.landed + tally.landed,
+        passed: sum.passed + tally.passed,
+    });
+    Answers {
+        part1: Ok(totals.landed.into()),
+        part2: Ok(totals.passed.into()),
+        additional_info: dial_info(tallies),
+        timings: None,
     }
 }
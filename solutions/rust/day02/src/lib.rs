@@ -0,0 +1,1140 @@
+use std::io::{self, Write};
+
+use aoc_core::{AocError, Solution};
+use rayon::prelude::*;
+use serde::Serialize;
+use serde_json::{Value, json};
+
+/// Day 2: sum the product IDs in each range that are a digit pattern repeated
+/// exactly twice (part 1) or two or more times (part 2).
+///
+/// Both parts are a [`RepetitionRule`] summed over each range.
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = Vec<(i64, i64)>;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_ranges(input)
+    }
+
+    fn part1(ranges: &Self::Input) -> Result<u128, AocError> {
+        Ok(sum_ranges(&RepetitionRule::PART1, ranges))
+    }
+
+    fn part2(ranges: &Self::Input) -> Result<u128, AocError> {
+        Ok(sum_ranges(&RepetitionRule::PART2, ranges))
+    }
+}
+
+/// Checks if a product ID string is made only of some sequence of digits repeated twice.
+/// Returns true if invalid (entire number is pattern repeated exactly twice), false if valid.
+///
+/// The number must be splittable into two equal halves that are identical.
+/// For example: 55 (5 repeated), 6464 (64 repeated), 123123 (123 repeated).
+///
+/// This is [`RepetitionRule::PART1`].
+///
+/// # Examples
+/// ```
+/// # use day02::is_invalid_id;
+/// assert_eq!(is_invalid_id("11"), true);      // "1" repeated
+/// assert_eq!(is_invalid_id("1212"), true);    // "12" repeated
+/// assert_eq!(is_invalid_id("123456"), false); // not a repeated pattern
+/// assert_eq!(is_invalid_id("123412"), false); // not split in half
+/// ```
+pub fn is_invalid_id(number_str: &str) -> bool {
+    RepetitionRule::PART1.matches_str(number_str)
+}
+
+/// Checks if a product ID string is made only of some sequence of digits repeated 2 or more times.
+/// Returns true if invalid (entire number is pattern repeated 2+ times), false if valid.
+///
+/// The number can be split into any equal-length pattern repeated at least twice.
+/// For example: 111 (1 repeated 3 times), 565656 (56 repeated 3 times), 2121212121 (21 repeated 5 times).
+///
+/// This is [`RepetitionRule::PART2`].
+///
+/// # Examples
+/// ```
+/// # use day02::is_invalid_id_part2;
+/// assert_eq!(is_invalid_id_part2("111"), true);        // "1" repeated 3 times
+/// assert_eq!(is_invalid_id_part2("565656"), true);     // "56" repeated 3 times
+/// assert_eq!(is_invalid_id_part2("123456"), false);    // not a repeated pattern
+/// assert_eq!(is_invalid_id_part2("11"), true);         // "1" repeated 2 times (still invalid)
+/// ```
+pub fn is_invalid_id_part2(number_str: &str) -> bool {
+    RepetitionRule::PART2.matches_str(number_str)
+}
+
+/// Parses comma-separated ranges from input string.
+/// Returns a vector of (start, end) tuples representing inclusive ranges.
+///
+/// # Examples
+/// ```
+/// # use day02::parse_ranges;
+/// let ranges = parse_ranges("11-22,95-115").unwrap();
+/// assert_eq!(ranges, vec![(11, 22), (95, 115)]);
+/// ```
+pub fn parse_ranges(input: &str) -> Result<Vec<(i64, i64)>, AocError> {
+    if input.trim().is_empty() {
+        return Err(AocError::parse("Empty input"));
+    }
+
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+
+    for raw in input.split(',') {
+        let range_str = raw.trim();
+        let start_offset = offset + (raw.len() - raw.trim_start().len());
+        offset += raw.len() + 1;
+        if range_str.is_empty() {
+            continue;
+        }
+
+        let parts: Vec<&str> = range_str.split('-').collect();
+        if parts.len() != 2 {
+            return Err(AocError::parse_at_offset(
+                input,
+                start_offset,
+                format!("Invalid range format: {}", range_str),
+            ));
+        }
+
+        let start = parts[0].trim().parse::<i64>().map_err(|e| {
+            AocError::parse_at_offset(
+                input,
+                start_offset,
+                format!("Invalid start number '{}': {}", parts[0], e),
+            )
+        })?;
+
+        let end = parts[1].trim().parse::<i64>().map_err(|e| {
+            AocError::parse_at_offset(
+                input,
+                start_offset + parts[0].len() + 1,
+                format!("Invalid end number '{}': {}", parts[1], e),
+            )
+        })?;
+
+        ranges.push((start, end));
+    }
+
+    Ok(ranges)
+}
+
+/// Number base an ID is written in before looking for repeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base {
+    Binary,
+    Decimal,
+    Hex,
+}
+
+impl Base {
+    pub fn radix(self) -> u32 {
+        match self {
+            Base::Binary => 2,
+            Base::Decimal => 10,
+            Base::Hex => 16,
+        }
+    }
+
+    /// `id` in this base, lowercase and without a prefix.
+    pub fn format(self, id: u64) -> String {
+        match self {
+            Base::Binary => format!("{:b}", id),
+            Base::Decimal => id.to_string(),
+            Base::Hex => format!("{:x}", id),
+        }
+    }
+
+    /// Digits in the largest ID, `i64::MAX`.
+    fn max_digits(self) -> u32 {
+        self.format(i64::MAX as u64).len() as u32
+    }
+
+    fn pow(self, exp: u32) -> i128 {
+        i128::from(self.radix()).pow(exp)
+    }
+
+    /// The number that repeats a `unit_len`-digit unit `repetitions` times
+    /// when multiplied by it: 1 + b^u + b^2u + ..., so 1212 = 12 × 101.
+    pub fn repetition_multiplier(self, unit_len: u32, repetitions: u32) -> i128 {
+        (0..repetitions).map(|i| self.pow(unit_len * i)).sum()
+    }
+
+    /// The units (with no leading zero) whose `repetitions`-fold repeat lies
+    /// in `start..=end`, as an inclusive range, or `None` if there are none.
+    fn unit_range(
+        self,
+        start: i64,
+        end: i64,
+        unit_len: u32,
+        repetitions: u32,
+    ) -> Option<(i128, i128)> {
+        let multiplier = self.repetition_multiplier(unit_len, repetitions);
+        let start = i128::from(start.max(1));
+        let end = i128::from(end);
+        let low = ((start + multiplier - 1) / multiplier).max(self.pow(unit_len - 1));
+        let high = (end.div_euclid(multiplier)).min(self.pow(unit_len) - 1);
+        (low <= high).then_some((low, high))
+    }
+
+    /// Sum of the IDs in `start..=end` made of a `unit_len`-digit unit
+    /// repeated exactly `repetitions` times: the multiplier times the sum of
+    /// the units, which form a contiguous range.
+    pub fn sum_repeated(self, start: i64, end: i64, unit_len: u32, repetitions: u32) -> i128 {
+        match self.unit_range(start, end, unit_len, repetitions) {
+            Some((low, high)) => {
+                self.repetition_multiplier(unit_len, repetitions) * (low + high) * (high - low + 1)
+                    / 2
+            }
+            None => 0,
+        }
+    }
+}
+
+/// When an ID counts as invalid: its digits are one unit repeated, with the
+/// repetition count and unit length within bounds.
+///
+/// A string matches if any split into equal units does, so under
+/// [`RepetitionRule::PART1`] 1111 is invalid as 11 repeated twice even though
+/// its shortest unit is 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RepetitionRule {
+    pub min_repetitions: u32,
+    /// No upper bound when `None`.
+    pub max_repetitions: Option<u32>,
+    pub min_unit_len: u32,
+    pub base: Base,
+}
+
+impl RepetitionRule {
+    /// A decimal unit repeated exactly twice.
+    pub const PART1: RepetitionRule = RepetitionRule {
+        min_repetitions: 2,
+        max_repetitions: Some(2),
+        min_unit_len: 1,
+        base: Base::Decimal,
+    };
+
+    /// A decimal unit repeated two or more times.
+    pub const PART2: RepetitionRule = RepetitionRule {
+        min_repetitions: 2,
+        max_repetitions: None,
+        min_unit_len: 1,
+        base: Base::Decimal,
+    };
+
+    /// Whether `unit_len`-long units repeated `repetitions` times are allowed.
+    fn allows(&self, unit_len: u32, repetitions: u32) -> bool {
+        unit_len >= self.min_unit_len
+            && repetitions >= self.min_repetitions
+            && self.max_repetitions.is_none_or(|max| repetitions <= max)
+    }
+
+    /// Checks any string, not just digits, so the rule doubles as a periodic
+    /// string detector. The base is ignored here.
+    pub fn matches_str(&self, s: &str) -> bool {
+        if s.is_ascii() {
+            self.matches_units(s.as_bytes())
+        } else {
+            self.matches_units(&s.chars().collect::<Vec<_>>())
+        }
+    }
+
+    fn matches_units<T: PartialEq>(&self, items: &[T]) -> bool {
+        let len = items.len() as u32;
+        (1..=len)
+            .filter(|&unit_len| len.is_multiple_of(unit_len))
+            .filter(|&unit_len| self.allows(unit_len, len / unit_len))
+            .any(|unit_len| {
+                let unit = &items[..unit_len as usize];
+                items.chunks(unit_len as usize).all(|chunk| chunk == unit)
+            })
+    }
+
+    /// Checks `id` written in the rule's base. Negative IDs never match.
+    pub fn matches(&self, id: i64) -> bool {
+        id >= 0 && self.matches_str(&self.base.format(id as u64))
+    }
+
+    /// Sum of the IDs in `start..=end` that match, in time proportional to
+    /// the number of digits rather than the size of the range.
+    ///
+    /// For each ID length `n` and each divisor `g` of `n`, it first finds the
+    /// sum of the IDs whose shortest unit is exactly `g` long: those made of
+    /// `g`-long units, less those whose shortest unit is a shorter divisor of
+    /// `g`. Such an ID can be split into units of every length that `g`
+    /// divides, so it matches when one of those lengths is allowed.
+    pub fn sum_in_range(&self, start: i64, end: i64) -> u128 {
+        let mut sum = 0i128;
+
+        for len in 1..=self.base.max_digits() {
+            let divisors: Vec<u32> = (1..=len).filter(|&d| len.is_multiple_of(d)).collect();
+            // Shortest-unit sums, filled in increasing order of unit length
+            let mut primitive: Vec<i128> = Vec::with_capacity(divisors.len());
+            for (i, &g) in divisors.iter().enumerate() {
+                let shorter: i128 = (0..i)
+                    .filter(|&j| g.is_multiple_of(divisors[j]))
+                    .map(|j| primitive[j])
+                    .sum();
+                primitive.push(self.base.sum_repeated(start, end, g, len / g) - shorter);
+
+                let matches = divisors
+                    .iter()
+                    .filter(|&&unit_len| unit_len.is_multiple_of(g))
+                    .any(|&unit_len| self.allows(unit_len, len / unit_len));
+                if matches {
+                    sum += primitive[i];
+                }
+            }
+        }
+
+        sum as u128
+    }
+}
+
+/// Splits `start..=end` where the IDs gain a digit in `base`, so each chunk
+/// holds IDs of a single length. A range up to `i64::MAX` is at most 19
+/// decimal chunks.
+pub fn split_by_length(start: i64, end: i64, base: Base) -> Vec<(i64, i64)> {
+    let mut chunks = Vec::new();
+    let mut low = start.max(1);
+    while low <= end {
+        // First ID with one more digit than `low`
+        let next = base.pow(base.format(low as u64).len() as u32);
+        let high = i128::from(end).min(next - 1) as i64;
+        chunks.push((low, high));
+        match i64::try_from(next) {
+            Ok(next) => low = next,
+            Err(_) => break,
+        }
+    }
+    chunks
+}
+
+/// Sums `rule` over every range, in parallel across the ranges and across
+/// the single-length chunks of each one ([`split_by_length`]).
+///
+/// The chunk sums are collected in input order and added up sequentially, so
+/// the result does not depend on how rayon schedules the work.
+pub fn sum_ranges(rule: &RepetitionRule, ranges: &[(i64, i64)]) -> u128 {
+    let chunks: Vec<(i64, i64)> = ranges
+        .iter()
+        .flat_map(|&(start, end)| split_by_length(start, end, rule.base))
+        .collect();
+    let sums: Vec<u128> = chunks
+        .par_iter()
+        .map(|&(start, end)| rule.sum_in_range(start, end))
+        .collect();
+    sums.into_iter().sum()
+}
+
+/// Sum of the IDs in `start..=end` that are a unit repeated exactly twice
+/// (part 1).
+pub fn sum_invalid_ids(start: i64, end: i64) -> u128 {
+    RepetitionRule::PART1.sum_in_range(start, end)
+}
+
+/// Processes a range of numbers and returns a vector of invalid IDs found.
+/// An ID is invalid if it's made only of some sequence repeated twice.
+///
+/// The IDs are built directly from their repeated halves rather than by
+/// checking every number in the range, so the cost is the number of IDs
+/// returned.
+///
+/// # Arguments
+/// * `start` - Starting number (inclusive)
+/// * `end` - Ending number (inclusive)
+///
+/// # Returns
+/// Vector of invalid product IDs found in the range, in ascending order
+pub fn process_range(start: i64, end: i64) -> Vec<i64> {
+    let base = Base::Decimal;
+    let mut invalid_ids = Vec::new();
+
+    for unit_len in 1..=base.max_digits() / 2 {
+        let multiplier = base.repetition_multiplier(unit_len, 2);
+        if let Some((low, high)) = base.unit_range(start, end, unit_len, 2) {
+            invalid_ids.extend((low..=high).map(|unit| (unit * multiplier) as i64));
+        }
+    }
+
+    invalid_ids
+}
+
+/// Processes a range of numbers and returns the sum of invalid IDs found (Part 2).
+/// An ID is invalid if it's made only of some sequence repeated 2 or more times.
+///
+/// # Arguments
+/// * `start` - Starting number (inclusive)
+/// * `end` - Ending number (inclusive)
+///
+/// # Returns
+/// Sum of all invalid product IDs found in the range
+pub fn process_range_part2(start: i64, end: i64) -> u128 {
+    RepetitionRule::PART2.sum_in_range(start, end)
+}
+
+/// An invalid ID found for the detailed report: `unit` repeated
+/// `repetitions` times, using the shortest unit (1111 is 1 × 4, not 11 × 2).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InvalidId {
+    pub id: i64,
+    pub unit: i64,
+    pub repetitions: u32,
+    /// Also invalid under part 1's repeated-exactly-twice rule, which holds
+    /// when the shortest unit repeats an even number of times.
+    pub part1: bool,
+}
+
+/// Every ID in `start..=end` invalid under part 2, in ascending order.
+///
+/// Each ID is built once from its shortest unit, which must not itself be a
+/// repetition, so the cost is about the number of IDs listed rather than the
+/// size of the range. A range spanning billions of IDs still lists every one
+/// of them.
+pub fn invalid_ids(start: i64, end: i64) -> Vec<InvalidId> {
+    // Chunks hold one ID length each, in ascending order, so concatenating
+    // their sorted IDs keeps the whole list sorted
+    split_by_length(start, end, Base::Decimal)
+        .par_iter()
+        .flat_map_iter(|&(low, high)| invalid_ids_of_one_length(low, high))
+        .collect()
+}
+
+fn invalid_ids_of_one_length(start: i64, end: i64) -> Vec<InvalidId> {
+    let base = Base::Decimal;
+    let len = base.format(start as u64).len() as u32;
+    let mut found = Vec::new();
+
+    for unit_len in (1..len).filter(|&unit_len| len.is_multiple_of(unit_len)) {
+        let repetitions = len / unit_len;
+        let multiplier = base.repetition_multiplier(unit_len, repetitions);
+        let Some((low, high)) = base.unit_range(start, end, unit_len, repetitions) else {
+            continue;
+        };
+        for unit in low..=high {
+            if unit_len > 1 && is_invalid_id_part2(&unit.to_string()) {
+                continue;
+            }
+            found.push(InvalidId {
+                id: (unit * multiplier) as i64,
+                unit: unit as i64,
+                repetitions,
+                part1: repetitions.is_multiple_of(2),
+            });
+        }
+    }
+
+    found.sort_unstable_by_key(|invalid| invalid.id);
+    found
+}
+
+/// The invalid IDs of one input range, for `"additional-info"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RangeDetails {
+    pub start: i64,
+    pub end: i64,
+    pub invalid: Vec<InvalidId>,
+}
+
+pub fn range_details(ranges: &[(i64, i64)]) -> Vec<RangeDetails> {
+    ranges
+        .par_iter()
+        .map(|&(start, end)| RangeDetails {
+            start,
+            end,
+            invalid: invalid_ids(start, end),
+        })
+        .collect()
+}
+
+/// [`Day02`] that also lists every invalid ID per range under
+/// `"additional-info"` (`day02 --details`).
+pub struct DetailedDay02;
+
+impl Solution for DetailedDay02 {
+    const DAY: u8 = 2;
+    type Input = Vec<(i64, i64)>;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Day02::parse(input)
+    }
+
+    fn part1(ranges: &Self::Input) -> Result<u128, AocError> {
+        Day02::part1(ranges)
+    }
+
+    fn part2(ranges: &Self::Input) -> Result<u128, AocError> {
+        Day02::part2(ranges)
+    }
+
+    fn additional_info(ranges: &Self::Input) -> Option<Value> {
+        Some(json!({ "ranges": range_details(ranges) }))
+    }
+}
+
+/// Writes one CSV row per invalid ID, with the range it was found in
+/// (`day02 --details-csv <PATH>`).
+pub fn write_details_csv(out: &mut impl Write, ranges: &[(i64, i64)]) -> io::Result<()> {
+    writeln!(out, "range_start,range_end,id,unit,repetitions,part1")?;
+    for &(start, end) in ranges {
+        for invalid in invalid_ids(start, end) {
+            writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                start, end, invalid.id, invalid.unit, invalid.repetitions, invalid.part1
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Main solution function that processes all ranges and computes the sum of invalid IDs.
+///
+/// # Arguments
+/// * `input` - Input string containing comma-separated ranges
+///
+/// # Returns
+/// Tuple of (part1_sum, Some(part2_sum))
+pub fn solve(input: &str) -> (u128, Option<u128>) {
+    let ranges = match parse_ranges(input) {
+        Ok(r) => r,
+        Err(e) => {
+            log::error!("Error parsing ranges: {}", e);
+            return (0, None);
+        }
+    };
+
+    let part1_sum = Day02::part1(&ranges).unwrap_or(0);
+    let part2_sum = Day02::part2(&ranges).ok();
+
+    (part1_sum, part2_sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Task 1.1: Pattern Detection Unit Tests
+
+    #[test]
+    fn test_single_repeated_digit() {
+        // Test "11" -> invalid (single digit "1" repeated twice)
+        assert!(is_invalid_id("11"));
+        // Additional test for "22"
+        assert!(is_invalid_id("22"));
+    }
+
+    #[test]
+    fn test_two_digit_repeated_pattern() {
+        // Test "1212" -> invalid (pattern "12" repeated twice)
+        assert!(is_invalid_id("1212"));
+    }
+
+    #[test]
+    fn test_longer_repeated_pattern() {
+        // Test "123123" -> invalid (pattern "123" repeated twice)
+        assert!(is_invalid_id("123123"));
+    }
+
+    #[test]
+    fn test_valid_no_repetition() {
+        // Test "123456" -> valid (not a repeated pattern)
+        assert!(!is_invalid_id("123456"));
+    }
+
+    #[test]
+    fn test_single_digit_valid() {
+        // Test "1" -> valid (odd length, cannot split in half)
+        assert!(!is_invalid_id("1"));
+        // Test "5" -> valid
+        assert!(!is_invalid_id("5"));
+    }
+
+    #[test]
+    fn test_mid_length_pattern() {
+        // Test "12345656" -> invalid ("5656" as second half equals "5656" split? No.)
+        // Actually, 12345656 split in half is "1234" and "5656" - not equal
+        assert!(!is_invalid_id("12345656"));
+    }
+
+    #[test]
+    fn test_partial_match() {
+        // Test "123412" -> valid (not split exactly in half: "123" != "412")
+        assert!(!is_invalid_id("123412"));
+    }
+
+    #[test]
+    fn test_multiple_overlapping_patterns() {
+        // Test "121212" -> invalid ("121" == "212"? No, "121" != "212")
+        // Wait, split "121212" in half: "121" and "212" - not equal, so valid!
+        // But this should be invalid per the requirements...
+        // Let me check: "121212" is "12" repeated 3 times, not 2 times
+        // For a pattern repeated exactly twice: we'd need the entire number to be split in half
+        // Actually, per problem "made only of some sequence of digits repeated twice"
+        // This means the sequence can be of any length, but must repeat exactly twice
+        // So "121212" could be interpreted as "121" + "212" (not equal) - valid
+        // OR as containing "12" repeated (but that's NOT the entire number)
+        // Based on examples: 55, 6464, 123123 - all split exactly in half
+        // So "121212" = "121" + "212" which are NOT equal, so this is actually VALID
+        // But wait, let me reconsider... NO, the problem says "repeated twice"
+        // I think I need to check if ANY starting position gives us a pattern repeated exactly twice
+        // Let me re-read requirements more carefully...
+        assert!(!is_invalid_id("121212"));
+    }
+
+    // Part 2 Pattern Detection Tests (Task 1.1)
+
+    #[test]
+    fn test_part2_single_char_three_reps() {
+        // Test "111" -> "1" repeated 3 times (invalid in Part 2)
+        assert!(is_invalid_id_part2("111"));
+        // Test "999" -> "9" repeated 3 times (invalid in Part 2)
+        assert!(is_invalid_id_part2("999"));
+    }
+
+    #[test]
+    fn test_part2_multi_char_three_reps() {
+        // Test "565656" -> "56" repeated 3 times (invalid in Part 2)
+        assert!(is_invalid_id_part2("565656"));
+        // Test "824824824" -> "824" repeated 3 times (invalid in Part 2)
+        assert!(is_invalid_id_part2("824824824"));
+    }
+
+    #[test]
+    fn test_part2_many_repetitions() {
+        // Test "2121212121" -> "21" repeated 5 times (invalid in Part 2)
+        assert!(is_invalid_id_part2("2121212121"));
+    }
+
+    #[test]
+    fn test_part2_edge_cases() {
+        // Test "11" -> still invalid in Part 2 (2 repetitions)
+        assert!(is_invalid_id_part2("11"));
+        // Test "123456" -> still valid in Part 2 (no repeating pattern)
+        assert!(!is_invalid_id_part2("123456"));
+    }
+
+    #[test]
+    fn test_part2_includes_part1_invalid() {
+        // All Part 1 invalid IDs should remain invalid in Part 2
+        assert!(is_invalid_id_part2("11"));
+        assert!(is_invalid_id_part2("1212"));
+        assert!(is_invalid_id_part2("123123"));
+        assert!(is_invalid_id_part2("99"));
+        assert!(is_invalid_id_part2("1010"));
+    }
+
+    // Task 1.2: Range-based Unit Tests
+
+    #[test]
+    fn test_range_11_to_22() {
+        // Range 11-22 should return [11, 22] (two invalid IDs)
+        let invalid_ids = process_range(11, 22);
+        assert_eq!(invalid_ids, vec![11, 22]);
+    }
+
+    #[test]
+    fn test_range_95_to_115() {
+        // Range 95-115 should return [99] (one invalid ID)
+        let invalid_ids = process_range(95, 115);
+        assert_eq!(invalid_ids, vec![99]);
+    }
+
+    #[test]
+    fn test_range_998_to_1012() {
+        // Range 998-1012 should return [1010] (one invalid ID)
+        let invalid_ids = process_range(998, 1012);
+        assert_eq!(invalid_ids, vec![1010]);
+    }
+
+    #[test]
+    fn test_range_1188511880_to_1188511890() {
+        // Range 1188511880-1188511890 should return [1188511885]
+        let invalid_ids = process_range(1188511880, 1188511890);
+        assert_eq!(invalid_ids, vec![1188511885]);
+    }
+
+    #[test]
+    fn test_range_222220_to_222224() {
+        // Range 222220-222224 should return [222222]
+        let invalid_ids = process_range(222220, 222224);
+        assert_eq!(invalid_ids, vec![222222]);
+    }
+
+    #[test]
+    fn test_range_1698522_to_1698528_no_invalid() {
+        // Range 1698522-1698528 should return [] (no invalid IDs)
+        let invalid_ids = process_range(1698522, 1698528);
+        assert_eq!(invalid_ids, Vec::<i64>::new());
+    }
+
+    #[test]
+    fn test_range_446443_to_446449() {
+        // Range 446443-446449 should return [446446]
+        let invalid_ids = process_range(446443, 446449);
+        assert_eq!(invalid_ids, vec![446446]);
+    }
+
+    #[test]
+    fn test_range_38593856_to_38593862() {
+        // Range 38593856-38593862 should return [38593859]
+        let invalid_ids = process_range(38593856, 38593862);
+        assert_eq!(invalid_ids, vec![38593859]);
+    }
+
+    // Additional helper tests
+
+    #[test]
+    fn test_parse_single_range() {
+        let ranges = parse_ranges("11-22").unwrap();
+        assert_eq!(ranges, vec![(11, 22)]);
+    }
+
+    #[test]
+    fn test_parse_multiple_ranges() {
+        let ranges = parse_ranges("11-22,95-115").unwrap();
+        assert_eq!(ranges, vec![(11, 22), (95, 115)]);
+    }
+
+    #[test]
+    fn test_parse_with_whitespace() {
+        let ranges = parse_ranges(" 11-22 , 95-115 ").unwrap();
+        assert_eq!(ranges, vec![(11, 22), (95, 115)]);
+    }
+
+    #[test]
+    fn test_parse_error_position() {
+        let err = parse_ranges("11-22, 95-x").unwrap_err();
+        assert!(matches!(
+            err,
+            AocError::Parse {
+                line: Some(1),
+                column: Some(11),
+                ..
+            }
+        ));
+        let err = parse_ranges("11-22,95").unwrap_err();
+        assert!(matches!(
+            err,
+            AocError::Parse {
+                column: Some(7),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_solve_simple_example() {
+        let input = "11-22";
+        let (sum, _) = solve(input);
+        // 11 + 22 = 33
+        assert_eq!(sum, 33);
+    }
+
+    // Additional verification tests for specific numbers
+
+    #[test]
+    fn test_specific_invalid_ids() {
+        // From problem: 55, 6464, 123123 should be invalid
+        assert!(is_invalid_id("55"));
+        assert!(is_invalid_id("6464"));
+        assert!(is_invalid_id("123123"));
+
+        // From ranges: these should be invalid
+        assert!(is_invalid_id("99"));
+        assert!(is_invalid_id("1010"));
+        assert!(is_invalid_id("222222"));
+        assert!(is_invalid_id("446446"));
+    }
+
+    // Task 2.1: Integration Tests
+
+    #[test]
+    fn test_integration_sample_input() {
+        // Test with the full sample input from problem statement
+        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+        let (sum, _) = solve(input);
+        // Expected sum from problem statement: 1227775554
+        assert_eq!(sum, 1227775554);
+    }
+
+    #[test]
+    fn test_integration_range_with_no_invalid_ids() {
+        // Test a range that has no invalid IDs
+        let input = "1698522-1698528";
+        let (sum, _) = solve(input);
+        assert_eq!(sum, 0);
+    }
+
+    #[test]
+    fn test_integration_multiple_ranges_sum() {
+        // Test multiple ranges and verify the sum
+        let input = "11-22,95-115"; // Should be 11 + 22 + 99 = 132
+        let (sum, _) = solve(input);
+        assert_eq!(sum, 132);
+    }
+
+    // Part 2 Integration Tests (Task 1.5)
+
+    #[test]
+    fn test_part2_integration_sample_input() {
+        // Test with the full sample input from problem statement
+        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+        let (part1_sum, part2_sum) = solve(input);
+
+        // Verify Part 1 sum remains correct
+        assert_eq!(part1_sum, 1227775554);
+
+        // Verify Part 2 sum
+        assert_eq!(part2_sum, Some(4174379265));
+    }
+
+    #[test]
+    fn test_part2_range_95_to_115() {
+        // Range 95-115: Part 2 should include [99, 111]
+        // 99 is "9" repeated, 111 is "1" repeated 3 times
+        let sum = process_range_part2(95, 115);
+        assert_eq!(sum, 99 + 111); // 210
+    }
+
+    #[test]
+    fn test_part2_range_998_to_1012() {
+        // Range 998-1012: Part 2 should include [999, 1010]
+        // 999 is "9" repeated 3 times, 1010 is "10" repeated
+        let sum = process_range_part2(998, 1012);
+        assert_eq!(sum, 999 + 1010); // 2009
+    }
+
+    #[test]
+    fn test_part2_range_565653_to_565659() {
+        // Range 565653-565659: Part 2 should include [565656]
+        // 565656 is "56" repeated 3 times
+        let sum = process_range_part2(565653, 565659);
+        assert_eq!(sum, 565656);
+    }
+
+    /// Sums the invalid IDs by checking every number in the range.
+    fn brute_force_sums(start: i64, end: i64) -> (u128, u128) {
+        (start..=end).fold((0, 0), |(part1, part2), num| {
+            let s = num.to_string();
+            let id = num as u128;
+            (
+                part1 + if is_invalid_id(&s) { id } else { 0 },
+                part2 + if is_invalid_id_part2(&s) { id } else { 0 },
+            )
+        })
+    }
+
+    #[test]
+    fn test_closed_form_matches_brute_force_up_to_a_million() {
+        assert_eq!(
+            (
+                sum_invalid_ids(1, 1_000_000),
+                process_range_part2(1, 1_000_000)
+            ),
+            brute_force_sums(1, 1_000_000)
+        );
+    }
+
+    #[test]
+    fn test_repetition_multiplier() {
+        assert_eq!(Base::Decimal.repetition_multiplier(2, 2), 101);
+        assert_eq!(Base::Decimal.repetition_multiplier(1, 3), 111);
+        assert_eq!(Base::Decimal.repetition_multiplier(3, 3), 1_001_001);
+        assert_eq!(Base::Binary.repetition_multiplier(2, 3), 0b10101);
+        assert_eq!(Base::Hex.repetition_multiplier(1, 2), 0x11);
+    }
+
+    #[test]
+    fn test_range_up_to_i64_max() {
+        // Every 2-digit ID repeats a 1-digit unit: 11 + 22 + ... + 99
+        assert_eq!(sum_invalid_ids(10, 99), 495);
+        // 19 is prime, so a 19-digit ID can only repeat one digit, and
+        // 9999999999999999999 is past i64::MAX
+        assert_eq!(
+            process_range_part2(8_888_888_888_888_888_888, i64::MAX),
+            8_888_888_888_888_888_888
+        );
+
+        let part1 = sum_invalid_ids(1, i64::MAX);
+        let part2 = process_range_part2(1, i64::MAX);
+        assert!(part1 > u128::from(u64::MAX));
+        assert!(part2 > part1);
+        assert_eq!(sum_invalid_ids(i64::MAX, i64::MAX), 0);
+        assert_eq!(process_range_part2(5, 1), 0);
+        assert_eq!(process_range_part2(-100, 11), 11);
+    }
+
+    #[test]
+    fn test_invalid_ids_use_shortest_unit() {
+        assert_eq!(
+            invalid_ids(1110, 1111),
+            vec![InvalidId {
+                id: 1111,
+                unit: 1,
+                repetitions: 4,
+                part1: true,
+            }]
+        );
+        let units: Vec<(i64, i64, u32, bool)> = invalid_ids(95, 1012)
+            .iter()
+            .map(|i| (i.id, i.unit, i.repetitions, i.part1))
+            .collect();
+        assert_eq!(
+            units,
+            vec![
+                (99, 9, 2, true),
+                (111, 1, 3, false),
+                (222, 2, 3, false),
+                (333, 3, 3, false),
+                (444, 4, 3, false),
+                (555, 5, 3, false),
+                (666, 6, 3, false),
+                (777, 7, 3, false),
+                (888, 8, 3, false),
+                (999, 9, 3, false),
+                (1010, 10, 2, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detailed_output() {
+        let ranges = parse_ranges("11-22,1698522-1698528").unwrap();
+        let info = DetailedDay02::additional_info(&ranges).unwrap();
+        assert_eq!(info["ranges"][0]["invalid"][1]["id"], 22);
+        assert_eq!(info["ranges"][1]["invalid"], json!([]));
+
+        let mut csv = Vec::new();
+        write_details_csv(&mut csv, &ranges).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "range_start,range_end,id,unit,repetitions,part1\n\
+             11,22,11,1,2,true\n\
+             11,22,22,2,2,true\n"
+        );
+    }
+
+    #[test]
+    fn test_rule_bounds() {
+        let three_or_four = RepetitionRule {
+            min_repetitions: 3,
+            max_repetitions: Some(4),
+            ..RepetitionRule::PART2
+        };
+        assert!(!three_or_four.matches(1212));
+        assert!(three_or_four.matches(121212));
+        assert!(three_or_four.matches(11111111)); // 11 four times
+        assert!(!three_or_four.matches(1212121212));
+
+        let long_units = RepetitionRule {
+            min_unit_len: 2,
+            ..RepetitionRule::PART2
+        };
+        assert!(!long_units.matches(111));
+        assert!(long_units.matches(1111)); // 11 twice
+        assert!(long_units.matches(123123));
+        assert!(!long_units.matches(-1111));
+    }
+
+    #[test]
+    fn test_rule_bases() {
+        let hex = RepetitionRule {
+            base: Base::Hex,
+            ..RepetitionRule::PART1
+        };
+        assert!(hex.matches(0xabab));
+        assert!(!hex.matches(0xabba));
+        let binary = RepetitionRule {
+            base: Base::Binary,
+            ..RepetitionRule::PART2
+        };
+        assert!(binary.matches(0b101101));
+        assert!(!binary.matches(0b1011));
+        // 1..=7 in binary: 1, 10, 11, 100, 101, 110, 111
+        assert_eq!(binary.sum_in_range(1, 7), 3 + 7);
+        assert_eq!(
+            hex.sum_in_range(0x10, 0xff),
+            (1..16).map(|d| d * 0x11).sum::<u128>()
+        );
+    }
+
+    #[test]
+    fn test_rule_as_string_detector() {
+        let rule = RepetitionRule::PART2;
+        assert!(rule.matches_str("abcabcabc"));
+        assert!(rule.matches_str("ééé"));
+        assert!(!rule.matches_str("abcab"));
+        assert!(!rule.matches_str(""));
+    }
+
+    #[test]
+    fn test_split_by_length() {
+        assert_eq!(
+            split_by_length(5, 1234, Base::Decimal),
+            vec![(5, 9), (10, 99), (100, 999), (1000, 1234)]
+        );
+        assert_eq!(split_by_length(-3, 2, Base::Decimal), vec![(1, 2)]);
+        assert_eq!(split_by_length(7, 6, Base::Decimal), vec![]);
+        assert_eq!(
+            split_by_length(2, 9, Base::Binary),
+            vec![(2, 3), (4, 7), (8, 9)]
+        );
+        let whole = split_by_length(1, i64::MAX, Base::Decimal);
+        assert_eq!(whole.len(), 19);
+        assert_eq!(whole.last(), Some(&(1_000_000_000_000_000_000, i64::MAX)));
+    }
+
+    #[test]
+    fn test_sum_ranges_matches_sequential_sum() {
+        let ranges = vec![
+            (1, i64::MAX),
+            (95, 115),
+            (5, 1),
+            (998, 1012),
+            (1, 1_000_000),
+        ];
+        for rule in [RepetitionRule::PART1, RepetitionRule::PART2] {
+            let sequential: u128 = ranges
+                .iter()
+                .map(|&(start, end)| rule.sum_in_range(start, end))
+                .sum();
+            assert_eq!(sum_ranges(&rule, &ranges), sequential);
+            assert_eq!(sum_ranges(&rule, &ranges), sum_ranges(&rule, &ranges));
+        }
+        assert_eq!(
+            invalid_ids(1, 1_000_000).len(),
+            (1..=1_000_000)
+                .filter(|id: &i64| is_invalid_id_part2(&id.to_string()))
+                .count()
+        );
+    }
+
+    // Property tests: the pattern-length scan against the doubled-string rotation check
+
+    use proptest::prelude::*;
+
+    /// A string is some block repeated at least twice exactly when it occurs
+    /// inside itself doubled with the first and last characters dropped.
+    fn is_repetition_naive(s: &str) -> bool {
+        if s.len() < 2 {
+            return false;
+        }
+        let doubled = s.repeat(2);
+        doubled[1..doubled.len() - 1].contains(s)
+    }
+
+    fn rule() -> impl Strategy<Value = RepetitionRule> {
+        (
+            1u32..4,
+            prop::option::of(1u32..6),
+            1u32..4,
+            prop_oneof![Just(Base::Binary), Just(Base::Decimal), Just(Base::Hex)],
+        )
+            .prop_map(|(min_repetitions, max_repetitions, min_unit_len, base)| {
+                RepetitionRule {
+                    min_repetitions,
+                    max_repetitions,
+                    min_unit_len,
+                    base,
+                }
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part2_matches_naive_check_on_numbers(n in 1u64..100_000_000_000) {
+            let s = n.to_string();
+            prop_assert_eq!(is_invalid_id_part2(&s), is_repetition_naive(&s));
+        }
+
+        #[test]
+        fn prop_part2_accepts_repeated_blocks(
+            block in "[1-9][0-9]{0,4}",
+            repetitions in 2usize..5,
+        ) {
+            let s = block.repeat(repetitions);
+            prop_assert!(is_invalid_id_part2(&s));
+            prop_assert!(is_repetition_naive(&s));
+        }
+
+        #[test]
+        fn prop_part1_is_the_two_repetition_case(
+            block in "[1-9][0-9]{0,5}",
+        ) {
+            let s = block.repeat(2);
+            prop_assert!(is_invalid_id(&s));
+            prop_assert!(is_invalid_id_part2(&s));
+        }
+
+        #[test]
+        fn prop_closed_form_sums_match_brute_force(
+            start in 1i64..1_000_000_000_000,
+            width in 0i64..2_000,
+        ) {
+            let end = start + width;
+            let (part1, part2) = brute_force_sums(start, end);
+            prop_assert_eq!(sum_invalid_ids(start, end), part1);
+            prop_assert_eq!(process_range_part2(start, end), part2);
+            prop_assert_eq!(
+                process_range(start, end).iter().map(|&id| id as u128).sum::<u128>(),
+                part1
+            );
+        }
+
+        #[test]
+        fn prop_closed_form_sums_match_brute_force_near_repeats(
+            unit in 1i64..100_000,
+            repetitions in 2usize..4,
+            before in 0i64..500,
+            after in 0i64..500,
+        ) {
+            let id: i64 = unit.to_string().repeat(repetitions).parse().unwrap();
+            let (start, end) = (id - before, id + after);
+            let (part1, part2) = brute_force_sums(start, end);
+            prop_assert_eq!(sum_invalid_ids(start, end), part1);
+            prop_assert_eq!(process_range_part2(start, end), part2);
+
+            let listed = invalid_ids(start, end);
+            let listed_sum = |part1_only: bool| {
+                listed
+                    .iter()
+                    .filter(|i| i.part1 || !part1_only)
+                    .map(|i| i.id as u128)
+                    .sum::<u128>()
+            };
+            prop_assert_eq!(listed_sum(true), part1);
+            prop_assert_eq!(listed_sum(false), part2);
+            for invalid in &listed {
+                prop_assert_eq!(
+                    invalid.unit.to_string().repeat(invalid.repetitions as usize),
+                    invalid.id.to_string()
+                );
+            }
+        }
+
+        #[test]
+        fn prop_rule_sum_matches_brute_force(
+            rule in rule(),
+            start in 0i64..100_000,
+            width in 0i64..3_000,
+        ) {
+            let end = start + width;
+            let brute: u128 = (start..=end)
+                .filter(|&id| rule.matches(id))
+                .map(|id| id as u128)
+                .sum();
+            prop_assert_eq!(rule.sum_in_range(start, end), brute);
+        }
+    }
+}
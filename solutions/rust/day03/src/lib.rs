@@ -0,0 +1,783 @@
+use aoc_core::{AocError, Solution};
+use std::str::FromStr;
+
+/// Day 3: sum the largest 2-digit (part 1) and 12-digit (part 2) numbers that
+/// can be formed from each battery bank by keeping digits in order.
+///
+/// A line may hold several banks separated by spaces or `|`; see
+/// [`parse_banks`]. `day03 --digits <K>` keeps K digits in part 2 instead of
+/// 12; see [`sum_max_subsequences`] for the limits.
+pub struct Day03;
+
+/// Digits kept per bank in part 1.
+pub const PART1_DIGITS: usize = 2;
+
+/// Digits kept per bank in part 2.
+pub const PART2_DIGITS: usize = 12;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input = Vec<String>;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_banks(input, InvalidChars::Reject)
+    }
+
+    fn part1(lines: &Self::Input) -> Result<u128, AocError> {
+        sum_max_subsequences(lines, PART1_DIGITS)
+    }
+
+    fn part2(lines: &Self::Input) -> Result<u128, AocError> {
+        sum_max_subsequences(lines, PART2_DIGITS)
+    }
+}
+
+/// What [`parse_banks`] does with a character that is neither a digit nor a
+/// bank separator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidChars {
+    /// Fail with a parse error pointing at the character.
+    #[default]
+    Reject,
+    /// Drop the character; the digits either side stay in one bank.
+    Skip,
+    /// Treat the character as another bank separator.
+    Split,
+}
+
+impl FromStr for InvalidChars {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reject" => Ok(InvalidChars::Reject),
+            "skip" => Ok(InvalidChars::Skip),
+            "split" => Ok(InvalidChars::Split),
+            other => Err(format!(
+                "unknown invalid-character policy '{}' (expected reject, skip or split)",
+                other
+            )),
+        }
+    }
+}
+
+/// Whether `c` ends one bank and starts the next.
+pub fn is_bank_separator(c: char) -> bool {
+    c.is_whitespace() || c == '|'
+}
+
+/// Splits one line into its banks: maximal runs of digits between
+/// separators (whitespace or `|`), with other characters handled by
+/// `policy`. Empty banks are dropped.
+///
+/// On [`InvalidChars::Reject`] the error is the 0-based character column and
+/// the offending character.
+pub fn tokenize_line(line: &str, policy: InvalidChars) -> Result<Vec<String>, (usize, char)> {
+    let mut banks = Vec::new();
+    let mut bank = String::new();
+    for (column, c) in line.chars().enumerate() {
+        if c.is_ascii_digit() {
+            bank.push(c);
+            continue;
+        }
+        match (is_bank_separator(c), policy) {
+            (true, _) | (false, InvalidChars::Split) => {
+                if !bank.is_empty() {
+                    banks.push(std::mem::take(&mut bank));
+                }
+            }
+            (false, InvalidChars::Skip) => {}
+            (false, InvalidChars::Reject) => return Err((column, c)),
+        }
+    }
+    if !bank.is_empty() {
+        banks.push(bank);
+    }
+    Ok(banks)
+}
+
+/// Every bank in the input, in order, as both parts see them. Each bank
+/// holds only ASCII digits.
+pub fn parse_banks(input: &str, policy: InvalidChars) -> Result<Vec<String>, AocError> {
+    let mut banks = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let tokens = tokenize_line(line, policy).map_err(|(column, bad)| {
+            AocError::parse_at(
+                index + 1,
+                column + 1,
+                format!("expected a digit or bank separator, found '{}'", bad),
+            )
+        })?;
+        banks.extend(tokens);
+    }
+    Ok(banks)
+}
+
+/// Every number of up to this many digits fits in a `u128`.
+pub const MAX_U128_DIGITS: usize = 38;
+
+/// Sums the largest k-digit number kept from each bank.
+///
+/// Fails with [`AocError::Infeasible`] when a bank has fewer than k digits,
+/// and rather than wrapping when a kept number or the sum does not fit in a
+/// `u128`, which is certain for k of 40 or more and possible from 39.
+pub fn sum_max_subsequences(banks: &[String], k: usize) -> Result<u128, AocError> {
+    let mut sum: u128 = 0;
+    for (index, bank) in banks.iter().enumerate() {
+        let kept = max_subsequence_number(bank, k).ok_or_else(|| {
+            AocError::infeasible(format!("bank {}: has fewer than {} digits", index + 1, k))
+        })?;
+        let value = kept.parse::<u128>().map_err(|_| {
+            AocError::infeasible(format!(
+                "bank {}: the {}-digit number {} does not fit in u128",
+                index + 1,
+                k,
+                kept
+            ))
+        })?;
+        sum = sum.checked_add(value).ok_or_else(|| {
+            AocError::infeasible(format!(
+                "the sum of the {}-digit numbers overflows u128 at bank {}",
+                k,
+                index + 1
+            ))
+        })?;
+    }
+    Ok(sum)
+}
+
+/// Extracts all possible 2-digit pairs from a line by picking any two positions
+///
+/// This is quadratic; part 1 uses [`max_subsequence_number`] instead. Like
+/// it, a line with any non-digit has no pairs; split input into banks with
+/// [`parse_banks`] first.
+///
+/// For each pair of positions (i, j) where i < j, creates a 2-digit number
+/// from digits at those positions. For example, "987" with positions (0,2) yields 97.
+pub fn extract_pairs(line: &str) -> Vec<i32> {
+    let mut pairs = Vec::new();
+    let chars: Vec<char> = line.chars().collect();
+
+    if chars.len() < 2 || !chars.iter().all(char::is_ascii_digit) {
+        return pairs;
+    }
+
+    for i in 0..chars.len() {
+        for j in (i + 1)..chars.len() {
+            // Create 2-digit number from positions i and j
+            let pair_str = format!("{}{}", chars[i], chars[j]);
+            if let Ok(pair) = pair_str.parse::<i32>() {
+                pairs.push(pair);
+            }
+        }
+    }
+
+    pairs
+}
+
+/// The largest k-digit number that can be formed from a line by keeping k
+/// of its digits in order, as a string of decimal digits so that any k fits.
+///
+/// Algorithm: a single pass with a monotonic stack. Each digit pops the
+/// smaller digits before it while enough digits remain to still keep k, then
+/// is pushed; the first k digits on the stack are the answer. Every digit is
+/// pushed and popped at most once, so this is O(n) for any k.
+///
+/// Keeping the lexicographically largest k digits gives the maximum numeric
+/// value, since every candidate has the same length.
+///
+/// # Returns
+/// * `Some(String)` - The k selected digits
+/// * `None` - If k is 0, the line has fewer than k digits or it contains
+///   non-digit characters
+///
+/// # Examples
+/// ```
+/// # use day03::max_subsequence_number;
+/// assert_eq!(max_subsequence_number("818181911112111", 2).as_deref(), Some("92"));
+/// assert_eq!(
+///     max_subsequence_number("9876543210987654321", 19).as_deref(),
+///     Some("9876543210987654321")
+/// );
+/// ```
+pub fn max_subsequence_number(line: &str, k: usize) -> Option<String> {
+    let digits = line.as_bytes();
+    let kept = max_subsequence_indices(line, k)?
+        .into_iter()
+        .map(|index| digits[index])
+        .collect();
+    Some(String::from_utf8(kept).expect("ASCII digits are valid UTF-8"))
+}
+
+/// Byte positions in `line` of the digits [`max_subsequence_number`] keeps,
+/// in increasing order. Where equal digits tie, the earliest ones are kept.
+///
+/// Returns `None` under the same conditions as [`max_subsequence_number`].
+pub fn max_subsequence_indices(line: &str, k: usize) -> Option<Vec<usize>> {
+    let digits = line.as_bytes();
+    if k == 0 || digits.len() < k || !digits.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+
+    // Digits that may still be dropped without leaving fewer than k
+    let mut droppable = digits.len() - k;
+    let mut stack: Vec<usize> = Vec::with_capacity(digits.len());
+    for (index, &digit) in digits.iter().enumerate() {
+        while droppable > 0 && stack.last().is_some_and(|&top| digits[top] < digit) {
+            stack.pop();
+            droppable -= 1;
+        }
+        stack.push(index);
+    }
+    stack.truncate(k);
+    Some(stack)
+}
+
+/// How [`render_selection`] marks the kept digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Highlight {
+    /// `[9]` around each kept digit; plain text for summaries and diffs.
+    #[default]
+    Brackets,
+    /// Bold green kept digits and dim dropped ones, for a terminal.
+    Ansi,
+}
+
+impl FromStr for Highlight {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "brackets" => Ok(Highlight::Brackets),
+            "ansi" => Ok(Highlight::Ansi),
+            other => Err(format!(
+                "unknown highlight style '{}' (expected ansi or brackets)",
+                other
+            )),
+        }
+    }
+}
+
+const ANSI_KEPT: &str = "\x1b[1;32m";
+const ANSI_DROPPED: &str = "\x1b[2m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Renders `line` with the digits at `selected` (increasing byte positions)
+/// marked in the given style.
+pub fn render_selection(line: &str, selected: &[usize], style: Highlight) -> String {
+    let mut out = String::with_capacity(line.len() * 3);
+    let mut selected = selected.iter().peekable();
+    for (index, ch) in line.char_indices() {
+        let kept = selected.next_if_eq(&&index).is_some();
+        match (style, kept) {
+            (Highlight::Brackets, true) => {
+                out.push('[');
+                out.push(ch);
+                out.push(']');
+            }
+            (Highlight::Brackets, false) => out.push(ch),
+            (Highlight::Ansi, true) => {
+                out.push_str(ANSI_KEPT);
+                out.push(ch);
+                out.push_str(ANSI_RESET);
+            }
+            (Highlight::Ansi, false) => {
+                out.push_str(ANSI_DROPPED);
+                out.push(ch);
+                out.push_str(ANSI_RESET);
+            }
+        }
+    }
+    out
+}
+
+/// One line per bank for `day03 --highlight`: the bank with its k kept digits
+/// marked, then the number they form. Banks shorter than k are shown
+/// unmarked with `-` for the number.
+pub fn highlight_banks(banks: &[String], k: usize, style: Highlight) -> Vec<String> {
+    banks
+        .iter()
+        .map(|bank| match max_subsequence_indices(bank, k) {
+            Some(selected) => {
+                let number: String = selected
+                    .iter()
+                    .map(|&index| char::from(bank.as_bytes()[index]))
+                    .collect();
+                format!("{}  {}", render_selection(bank, &selected, style), number)
+            }
+            None => format!("{}  -", bank),
+        })
+        .collect()
+}
+
+/// Removes `--highlight` and the style after it, if it names one, from the
+/// arguments. A bare `--highlight` means [`Highlight::Brackets`].
+pub fn take_highlight_flag(args: &mut Vec<String>) -> Result<Option<Highlight>, String> {
+    let Some(index) = args.iter().position(|arg| arg == "--highlight") else {
+        return Ok(None);
+    };
+    args.remove(index);
+    let style = match args.get(index).map(|next| next.parse::<Highlight>()) {
+        Some(Ok(style)) => {
+            args.remove(index);
+            style
+        }
+        _ => Highlight::default(),
+    };
+    if args.iter().any(|arg| arg == "--highlight") {
+        return Err("--highlight given more than once".to_string());
+    }
+    Ok(Some(style))
+}
+
+/// Extracts the maximum k-digit number from a line as an `i64`.
+///
+/// # Arguments
+/// * `line` - String containing digit characters
+/// * `k` - Number of digits to extract
+///
+/// # Returns
+/// * `Some(i64)` - The maximum k-digit number as a 64-bit integer
+/// * `None` - If the line has fewer than k digits or contains non-digit characters,
+///   or the number does not fit in an `i64` (k of 19 or more, in general)
+///
+/// # Examples
+/// ```
+/// # use day03::extract_max_k_digits;
+/// assert_eq!(extract_max_k_digits("987654321111111", 12), Some(987654321111));
+/// assert_eq!(extract_max_k_digits("811111111111119", 12), Some(811111111119));
+/// ```
+pub fn extract_max_k_digits(line: &str, k: usize) -> Option<i64> {
+    max_subsequence_number(line, k)?.parse::<i64>().ok()
+}
+
+/// Finds the maximum value from a list of pairs
+pub fn find_max(pairs: &[i32]) -> Option<i32> {
+    pairs.iter().max().copied()
+}
+
+/// Solves the puzzle: returns (part1_sum, part2_sum) as a tuple
+///
+/// Part 1: Sum of maximum 2-digit pairs from each line
+/// Part 2: Sum of maximum 12-digit numbers from each line
+///
+/// Input with an invalid character is an error, never a zero answer.
+pub fn solve(input: &str) -> Result<(u128, u128), AocError> {
+    let lines = Day03::parse(input)?;
+    let part1_sum = Day03::part1(&lines)?;
+    let part2_sum = Day03::part2(&lines)?;
+
+    Ok((part1_sum, part2_sum))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ========== Part 1 Tests (Existing) ==========
+
+    #[test]
+    fn test_extract_pairs_simple() {
+        // Test "987" yields pairs including 98, 97, 87 among others
+        let pairs = extract_pairs("987");
+        assert!(pairs.contains(&98)); // positions 0,1
+        assert!(pairs.contains(&97)); // positions 0,2
+        assert!(pairs.contains(&87)); // positions 1,2
+    }
+
+    #[test]
+    fn test_find_max_from_pairs() {
+        // Test finding maximum from a list of pairs
+        let pairs = vec![98, 87, 76, 65, 54, 43, 32, 21, 11];
+        assert_eq!(find_max(&pairs), Some(98));
+    }
+
+    #[test]
+    fn test_sample_input_line1() {
+        // Test line 1: "987654321111111" → max = 98
+        let line = "987654321111111";
+        let pairs = extract_pairs(line);
+        let max = find_max(&pairs);
+        assert_eq!(max, Some(98));
+    }
+
+    #[test]
+    fn test_sample_input_line2() {
+        // Test line 2: "811111111111119" → max = 89 (positions 0 and 14: '8' and '9')
+        let line = "811111111111119";
+        let pairs = extract_pairs(line);
+        let max = find_max(&pairs);
+        assert_eq!(max, Some(89));
+    }
+
+    #[test]
+    fn test_sample_input_line3() {
+        // Test line 3: "234234234234278" → max = 78
+        let line = "234234234234278";
+        let pairs = extract_pairs(line);
+        let max = find_max(&pairs);
+        assert_eq!(max, Some(78));
+    }
+
+    #[test]
+    fn test_sample_input_line4() {
+        // Test line 4: "818181911112111" → max = 92
+        let line = "818181911112111";
+        let pairs = extract_pairs(line);
+        let max = find_max(&pairs);
+        assert_eq!(max, Some(92));
+    }
+
+    #[test]
+    fn test_complete_sample_input() {
+        // Test complete sample input expecting sum of 357
+        let input = "987654321111111\n811111111111119\n234234234234278\n818181911112111";
+        let (part1, _) = solve(input).unwrap();
+        assert_eq!(part1, 357);
+    }
+
+    #[test]
+    fn test_short_string() {
+        // Test single character returns empty
+        let pairs = extract_pairs("9");
+        assert_eq!(pairs.len(), 0);
+        assert_eq!(find_max(&pairs), None);
+    }
+
+    // ========== Part 2 Tests (New) ==========
+
+    #[test]
+    fn test_extract_max_k_digits_line1() {
+        // Test line 1: "987654321111111" yields 987654321111
+        // Remove three 1s from end
+        let line = "987654321111111";
+        let result = extract_max_k_digits(line, 12);
+        assert_eq!(result, Some(987654321111));
+    }
+
+    #[test]
+    fn test_extract_max_k_digits_line2() {
+        // Test line 2: "811111111111119" yields 811111111119
+        // Remove three 1s from middle
+        let line = "811111111111119";
+        let result = extract_max_k_digits(line, 12);
+        assert_eq!(result, Some(811111111119));
+    }
+
+    #[test]
+    fn test_extract_max_k_digits_line3() {
+        // Test line 3: "234234234234278" yields 434234234278
+        // Remove 2, 3, 2 from start
+        let line = "234234234234278";
+        let result = extract_max_k_digits(line, 12);
+        assert_eq!(result, Some(434234234278));
+    }
+
+    #[test]
+    fn test_extract_max_k_digits_line4() {
+        // Test line 4: "818181911112111" yields 888911112111
+        // Remove some 1s from start
+        let line = "818181911112111";
+        let result = extract_max_k_digits(line, 12);
+        assert_eq!(result, Some(888911112111));
+    }
+
+    // ========== Integration Tests ==========
+
+    #[test]
+    fn test_solve_returns_tuple() {
+        // Test that solve() returns (part1, part2) tuple for sample input
+        let input = "987654321111111\n811111111111119\n234234234234278\n818181911112111";
+        let (part1, part2) = solve(input).unwrap();
+        assert_eq!(part1, 357);
+        assert_eq!(part2, 3121910778619);
+    }
+
+    #[test]
+    fn test_part1_unchanged() {
+        // Verify Part 1 result remains unchanged
+        let input = "987654321111111\n811111111111119\n234234234234278\n818181911112111";
+        let (part1, _) = solve(input).unwrap();
+        assert_eq!(part1, 357);
+    }
+
+    #[test]
+    fn test_parse_rejects_non_digits() {
+        let err = Day03::parse("987\n  81x1\n").unwrap_err();
+        assert_eq!(
+            err,
+            AocError::parse_at(2, 5, "expected a digit or bank separator, found 'x'")
+        );
+    }
+
+    #[test]
+    fn test_solve_rejects_non_digits() {
+        assert_eq!(
+            solve("987\n  81x1\n").unwrap_err(),
+            AocError::parse_at(2, 5, "expected a digit or bank separator, found 'x'")
+        );
+    }
+
+    #[test]
+    fn test_parse_banks_splits_lines() {
+        let input = "987 654|321\n  12 | | 34  \n\n5";
+        assert_eq!(
+            parse_banks(input, InvalidChars::Reject).unwrap(),
+            vec!["987", "654", "321", "12", "34", "5"]
+        );
+        assert_eq!(
+            sum_max_subsequences(&parse_banks("98|11 95\n", InvalidChars::Reject).unwrap(), 2),
+            Ok(98 + 11 + 95)
+        );
+    }
+
+    #[test]
+    fn test_invalid_char_policies() {
+        let line = "81x19-2";
+        assert_eq!(tokenize_line(line, InvalidChars::Reject), Err((2, 'x')));
+        assert_eq!(
+            tokenize_line(line, InvalidChars::Skip),
+            Ok(vec!["81192".to_string()])
+        );
+        assert_eq!(
+            tokenize_line(line, InvalidChars::Split),
+            Ok(vec!["81".to_string(), "19".to_string(), "2".to_string()])
+        );
+        assert_eq!("skip".parse(), Ok(InvalidChars::Skip));
+        assert!("drop".parse::<InvalidChars>().is_err());
+    }
+
+    #[test]
+    fn test_parts_agree_on_banks() {
+        // A line with a stray character is no bank to either helper
+        assert!(extract_pairs("9x8").is_empty());
+        assert_eq!(max_subsequence_number("9x8", 2), None);
+    }
+
+    #[test]
+    fn test_part2_correct_sum() {
+        // Verify Part 2 produces correct sum
+        let input = "987654321111111\n811111111111119\n234234234234278\n818181911112111";
+        let (_, part2) = solve(input).unwrap();
+        assert_eq!(part2, 3121910778619);
+    }
+
+    #[test]
+    fn test_max_subsequence_number_beyond_i64() {
+        // Only five digits can go: the first five 8s
+        let line = "98".repeat(15);
+        let kept = max_subsequence_number(&line, 25).unwrap();
+        assert_eq!(kept, "9".repeat(5) + &"98".repeat(10));
+        assert_eq!(extract_max_k_digits(&line, 25), None);
+        assert_eq!(max_subsequence_number("123", 0), None);
+        assert_eq!(max_subsequence_number("12a", 2), None);
+    }
+
+    #[test]
+    fn test_sum_max_subsequences_wide_selections() {
+        let banks = vec!["98".repeat(15), "1".repeat(40)];
+        // k = 20: ten 9s then the last five 98 pairs; forty 1s keep twenty
+        let nines = "9".repeat(10) + &"98".repeat(5);
+        let expected = nines.parse::<u128>().unwrap() + "1".repeat(20).parse::<u128>().unwrap();
+        assert_eq!(sum_max_subsequences(&banks, 20), Ok(expected));
+
+        // k = 30 keeps every digit of the first bank
+        let expected =
+            "98".repeat(15).parse::<u128>().unwrap() + "1".repeat(30).parse::<u128>().unwrap();
+        assert_eq!(sum_max_subsequences(&banks, 30), Ok(expected));
+    }
+
+    #[test]
+    fn test_sum_max_subsequences_rejects_short_banks() {
+        // As `day03 --sample --digits 40`: every sample bank has 15 digits
+        let input = "987654321111111\n811111111111119\n234234234234278\n818181911112111";
+        let banks = Day03::parse(input).unwrap();
+        let err = sum_max_subsequences(&banks, 40).unwrap_err();
+        assert_eq!(
+            err,
+            AocError::infeasible("bank 1: has fewer than 40 digits")
+        );
+        assert_eq!(err.exit_code(), 5);
+
+        assert_eq!(
+            solve("98|11 95\n").unwrap_err(),
+            AocError::infeasible("bank 1: has fewer than 12 digits")
+        );
+    }
+
+    #[test]
+    fn test_sum_max_subsequences_u128_limits() {
+        let nines = vec!["9".repeat(MAX_U128_DIGITS)];
+        assert_eq!(
+            sum_max_subsequences(&nines, MAX_U128_DIGITS),
+            Ok(10u128.pow(38) - 1)
+        );
+
+        let too_wide = vec!["9".repeat(39)];
+        assert!(matches!(
+            sum_max_subsequences(&too_wide, 39),
+            Err(AocError::Infeasible { .. })
+        ));
+
+        // Each value fits but their sum does not
+        let big = vec!["3".repeat(39), "3".repeat(39)];
+        assert!(matches!(
+            sum_max_subsequences(&big, 39),
+            Err(AocError::Infeasible { .. })
+        ));
+        assert!(sum_max_subsequences(&big[..1], 39).is_ok());
+    }
+
+    #[test]
+    fn test_max_subsequence_indices() {
+        assert_eq!(
+            max_subsequence_indices("818181911112111", 2),
+            Some(vec![6, 11])
+        );
+        // Ties keep the earliest of the equal digits
+        assert_eq!(max_subsequence_indices("9919", 2), Some(vec![0, 1]));
+        assert_eq!(max_subsequence_indices("12", 3), None);
+    }
+
+    #[test]
+    fn test_render_selection() {
+        let line = "818181911112111";
+        let selected = max_subsequence_indices(line, 2).unwrap();
+        assert_eq!(
+            render_selection(line, &selected, Highlight::Brackets),
+            "818181[9]1111[2]111"
+        );
+        assert_eq!(
+            render_selection("91", &[0], Highlight::Ansi),
+            "\x1b[1;32m9\x1b[0m\x1b[2m1\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_highlight_banks() {
+        let banks = vec!["987654321111111".to_string(), "12".to_string()];
+        assert_eq!(
+            highlight_banks(&banks, 3, Highlight::Brackets),
+            vec!["[9][8][7]654321111111  987", "12  -"]
+        );
+    }
+
+    #[test]
+    fn test_take_highlight_flag() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        let mut plain = args(&["--sample"]);
+        assert_eq!(take_highlight_flag(&mut plain), Ok(None));
+
+        let mut bare = args(&["--highlight", "--sample"]);
+        assert_eq!(
+            take_highlight_flag(&mut bare),
+            Ok(Some(Highlight::Brackets))
+        );
+        assert_eq!(bare, args(&["--sample"]));
+
+        let mut ansi = args(&["--highlight", "ansi"]);
+        assert_eq!(take_highlight_flag(&mut ansi), Ok(Some(Highlight::Ansi)));
+        assert!(ansi.is_empty());
+
+        assert!(take_highlight_flag(&mut args(&["--highlight", "--highlight"])).is_err());
+    }
+
+    // Property tests: greedy digit selection against brute force over every subsequence
+
+    use proptest::prelude::*;
+
+    /// Tries every way of keeping `k` of the digits in order and returns the largest.
+    fn brute_force_max(digits: &[u8], k: usize) -> i64 {
+        let n = digits.len();
+        (0u32..1 << n)
+            .filter(|mask| mask.count_ones() as usize == k)
+            .map(|mask| {
+                (0..n)
+                    .filter(|i| mask & (1 << i) != 0)
+                    .fold(0i64, |acc, i| acc * 10 + i64::from(digits[i]))
+            })
+            .max()
+            .unwrap()
+    }
+
+    /// The window-scanning greedy selection that the stack version replaced:
+    /// for each output digit, the first largest digit that still leaves room
+    /// for the rest.
+    fn greedy_window(line: &str, k: usize) -> String {
+        let digits = line.as_bytes();
+        let mut kept = String::new();
+        let mut from = 0;
+        for remaining in (1..=k).rev() {
+            let window = &digits[from..=digits.len() - remaining];
+            let best = *window.iter().max().unwrap();
+            from += window.iter().position(|&d| d == best).unwrap() + 1;
+            kept.push(char::from(best));
+        }
+        kept
+    }
+
+    proptest! {
+        #[test]
+        fn prop_extract_max_k_digits_matches_brute_force(
+            (digits, k) in prop::collection::vec(1u8..=9, 1..=12)
+                .prop_flat_map(|digits| {
+                    let len = digits.len();
+                    (Just(digits), 1..=len)
+                }),
+        ) {
+            let line: String = digits.iter().map(|d| char::from(b'0' + d)).collect();
+            prop_assert_eq!(extract_max_k_digits(&line, k), Some(brute_force_max(&digits, k)));
+        }
+
+        #[test]
+        fn prop_indices_spell_the_kept_number(
+            line in "[0-9]{1,40}",
+            k in 1usize..=40,
+        ) {
+            let number = max_subsequence_number(&line, k);
+            let spelled = max_subsequence_indices(&line, k).map(|indices| {
+                prop_assert!(indices.windows(2).all(|pair| pair[0] < pair[1]));
+                Ok(indices.iter().map(|&i| char::from(line.as_bytes()[i])).collect::<String>())
+            });
+            prop_assert_eq!(spelled.transpose()?, number);
+        }
+
+        #[test]
+        fn prop_extract_max_k_digits_rejects_short_lines(
+            line in "[1-9]{0,8}",
+            extra in 1usize..4,
+        ) {
+            prop_assert_eq!(extract_max_k_digits(&line, line.len() + extra), None);
+        }
+
+        #[test]
+        fn prop_extract_max_k_digits_rejects_non_digits(
+            prefix in "[1-9]{0,5}",
+            bad in "[a-z ]",
+            suffix in "[1-9]{0,5}",
+        ) {
+            let line = format!("{prefix}{bad}{suffix}");
+            prop_assert_eq!(extract_max_k_digits(&line, line.len()), None);
+        }
+
+        #[test]
+        fn prop_max_subsequence_number_matches_greedy_window(
+            (line, k) in "[0-9]{1,60}".prop_flat_map(|line| {
+                let len = line.len();
+                (Just(line), 1..=len)
+            }),
+        ) {
+            prop_assert_eq!(max_subsequence_number(&line, k), Some(greedy_window(&line, k)));
+        }
+
+        #[test]
+        fn prop_part1_matches_all_pairs(line in "[1-9]{2,30}") {
+            let best_pair = find_max(&extract_pairs(&line));
+            let kept = max_subsequence_number(&line, 2).and_then(|s| s.parse::<i32>().ok());
+            prop_assert_eq!(kept, best_pair);
+        }
+    }
+}
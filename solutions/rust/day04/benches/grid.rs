@@ -0,0 +1,60 @@
+//! Benchmarks for the packed grid: the bit-parallel accessibility scan
+//! (part 1) and the worklist removal (part 2).
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use day04::RollGrid;
+
+/// A `size` x `size` warehouse with a roll on about 60% of the cells, from a
+/// fixed xorshift seed so every run sees the same floor.
+fn warehouse(size: usize) -> String {
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut text = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        for _ in 0..size {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            text.push(if state % 10 < 6 { '@' } else { '.' });
+        }
+        text.push('\n');
+    }
+    text
+}
+
+fn inputs() -> Vec<(String, RollGrid)> {
+    let sample = std::fs::read_to_string(aoc_core::input_path(4, true)).unwrap();
+    let mut inputs = vec![("sample".to_string(), RollGrid::parse(&sample))];
+    for size in [100, 1000, 3000] {
+        inputs.push((
+            format!("warehouse-{}", size),
+            RollGrid::parse(&warehouse(size)),
+        ));
+    }
+    inputs
+}
+
+fn bench_accessible(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day04/accessible");
+    for (name, grid) in inputs() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &grid, |b, grid| {
+            b.iter(|| black_box(grid).accessible())
+        });
+    }
+    group.finish();
+}
+
+fn bench_remove_in_waves(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day04/remove_in_waves");
+    group.sample_size(10);
+    for (name, grid) in inputs() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &grid, |b, grid| {
+            b.iter(|| black_box(grid).remove_in_waves(|_| {}))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_accessible, bench_remove_in_waves);
+criterion_main!(benches);
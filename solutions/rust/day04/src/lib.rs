@@ -0,0 +1,1659 @@
+use aoc_core::{AocError, Solution};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+/// Day 4: count paper rolls reachable by forklift (fewer than 4 neighbouring
+/// rolls), then keep removing reachable rolls until none are left (part 2).
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = RollGrid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(RollGrid::parse(input))
+    }
+
+    fn part1(grid: &Self::Input) -> Result<usize, AocError> {
+        Ok(grid.accessible().count())
+    }
+
+    fn part2(grid: &Self::Input) -> Result<usize, AocError> {
+        Ok(grid.remove_in_waves(|_| {}))
+    }
+
+    fn additional_info(grid: &Self::Input) -> Option<Value> {
+        Some(grid_info(grid, &Rules::default(), false))
+    }
+}
+
+/// A roll is accessible with fewer than this many neighbouring rolls.
+pub const ACCESS_LIMIT: u8 = 4;
+
+/// Marks a cell with no roll (or one already removed) in a count table.
+const GONE: u8 = u8::MAX;
+
+/// The warehouse floor as packed bits: one bit per cell, set where there is
+/// a roll. Each row is padded to whole `u64` words and the padding is always
+/// clear, so neighbour counts can be done a word (64 cells) at a time.
+///
+/// Cells are also addressed by flat index `row * cols + col`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollGrid {
+    rows: usize,
+    cols: usize,
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+/// A 3-bit saturating counter per bit lane: `low` and `high` hold counts
+/// 0 to 3 and `full` sticks once a lane reaches [`ACCESS_LIMIT`].
+#[derive(Default)]
+struct LaneCounter {
+    low: u64,
+    high: u64,
+    full: u64,
+}
+
+impl LaneCounter {
+    fn add(&mut self, lanes: u64) {
+        let carry = self.low & lanes;
+        self.low ^= lanes;
+        self.full |= self.high & carry;
+        self.high ^= carry;
+    }
+}
+
+impl RollGrid {
+    /// An empty floor of the given size.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let words_per_row = cols.div_ceil(64);
+        RollGrid {
+            rows,
+            cols,
+            words_per_row,
+            bits: vec![0; rows * words_per_row],
+        }
+    }
+
+    /// Reads `@` as a roll and anything else as floor. Lines are trimmed and
+    /// blank lines skipped; short rows are padded with floor.
+    pub fn parse(input: &str) -> Self {
+        RollGrid::parse_with(input, &Symbols::default())
+    }
+
+    /// [`RollGrid::parse`] with `symbols.roll` marking a roll. Whitespace
+    /// that is one of the symbols is kept.
+    pub fn parse_with(input: &str, symbols: &Symbols) -> Self {
+        let padding = |c: char| c.is_whitespace() && c != symbols.roll && c != symbols.floor;
+        let lines: Vec<&str> = input
+            .lines()
+            .map(|line| line.trim_matches(padding))
+            .filter(|line| !line.is_empty())
+            .collect();
+        let cols = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut grid = RollGrid::new(lines.len(), cols);
+        for (row, line) in lines.iter().enumerate() {
+            for (col, cell) in line.chars().enumerate() {
+                if cell == symbols.roll {
+                    grid.insert(row * cols + col);
+                }
+            }
+        }
+        grid
+    }
+
+    /// Packs a character grid; see [`RollGrid::parse`].
+    pub fn from_chars(grid: &[Vec<char>]) -> Self {
+        let cols = grid.iter().map(Vec::len).max().unwrap_or(0);
+        let mut packed = RollGrid::new(grid.len(), cols);
+        for (row, line) in grid.iter().enumerate() {
+            for (col, &cell) in line.iter().enumerate() {
+                if cell == '@' {
+                    packed.insert(row * cols + col);
+                }
+            }
+        }
+        packed
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The (row, col) of a flat index.
+    pub fn position(&self, index: usize) -> (usize, usize) {
+        (index / self.cols, index % self.cols)
+    }
+
+    fn slot(&self, index: usize) -> (usize, u64) {
+        let (row, col) = self.position(index);
+        (row * self.words_per_row + col / 64, 1 << (col % 64))
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        let (word, bit) = self.slot(index);
+        self.bits[word] & bit != 0
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        let (word, bit) = self.slot(index);
+        self.bits[word] |= bit;
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        let (word, bit) = self.slot(index);
+        self.bits[word] &= !bit;
+    }
+
+    /// Number of rolls.
+    pub fn count(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Flat indices of every roll, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bits.iter().enumerate().flat_map(move |(slot, &word)| {
+            let row = slot / self.words_per_row;
+            let base = (slot % self.words_per_row) * 64;
+            BitIter(word).map(move |bit| row * self.cols + base + bit)
+        })
+    }
+
+    /// Word `word` of `row`, with the same word for the rows above and below
+    /// shifted so that bit `c` holds column `c - 1` (`west`) or `c + 1`
+    /// (`east`).
+    fn shifted(&self, row: usize, word: usize) -> [u64; 3] {
+        let words = &self.bits[row * self.words_per_row..(row + 1) * self.words_per_row];
+        let here = words[word];
+        let before = if word > 0 { words[word - 1] } else { 0 };
+        let after = words.get(word + 1).copied().unwrap_or(0);
+        let west = (here << 1) | (before >> 63);
+        let east = (here >> 1) | (after << 63);
+        [west, here, east]
+    }
+
+    /// The rolls with fewer than [`ACCESS_LIMIT`] neighbouring rolls among
+    /// the 8 around them.
+    ///
+    /// Bit-parallel: the 8 neighbour masks of a word are shifted copies of
+    /// the words above, beside and below it, summed lane by lane in a
+    /// saturating counter, so each word of 64 cells costs a few dozen
+    /// operations.
+    pub fn accessible(&self) -> RollGrid {
+        let mut accessible = RollGrid::new(self.rows, self.cols);
+        for row in 0..self.rows {
+            for word in 0..self.words_per_row {
+                let rolls = self.bits[row * self.words_per_row + word];
+                if rolls == 0 {
+                    continue;
+                }
+                let mut counter = LaneCounter::default();
+                for neighbour_row in row.saturating_sub(1)..(row + 2).min(self.rows) {
+                    let [west, here, east] = self.shifted(neighbour_row, word);
+                    counter.add(west);
+                    counter.add(east);
+                    if neighbour_row != row {
+                        counter.add(here);
+                    }
+                }
+                accessible.bits[row * self.words_per_row + word] = rolls & !counter.full;
+            }
+        }
+        accessible
+    }
+
+    /// Number of rolls among the 8 cells around every cell, by flat index.
+    pub fn neighbour_counts(&self) -> Vec<u8> {
+        let width = self.cols + 2;
+        let padded = self.padded_counts(false);
+        (0..self.rows)
+            .flat_map(|row| &padded[(row + 1) * width + 1..(row + 2) * width - 1])
+            .copied()
+            .collect()
+    }
+
+    /// Neighbour counts on the grid with a one-cell border of [`GONE`] all
+    /// round, `cols + 2` wide, so that every cell of the grid has all 8
+    /// neighbours at fixed offsets. Floor cells are [`GONE`] too when
+    /// `mark_floor` is set.
+    ///
+    /// A 3x3 box convolution over three rolling rows of 0/1 cells.
+    fn padded_counts(&self, mark_floor: bool) -> Vec<u8> {
+        let width = self.cols + 2;
+        let mut counts = vec![GONE; (self.rows + 2) * width];
+        let unpack = |row: usize, cells: &mut [u8]| {
+            let words = &self.bits[row * self.words_per_row..(row + 1) * self.words_per_row];
+            for col in 0..self.cols {
+                cells[col + 1] = ((words[col / 64] >> (col % 64)) & 1) as u8;
+            }
+        };
+
+        let mut above = vec![0u8; width];
+        let mut here = vec![0u8; width];
+        let mut below = vec![0u8; width];
+        if self.rows > 0 {
+            unpack(0, &mut here);
+        }
+        for row in 0..self.rows {
+            if row + 1 < self.rows {
+                unpack(row + 1, &mut below);
+            } else {
+                below.fill(0);
+            }
+            let out = &mut counts[(row + 1) * width..(row + 2) * width];
+            for col in 1..=self.cols {
+                let sum = above[col - 1]
+                    + above[col]
+                    + above[col + 1]
+                    + here[col - 1]
+                    + here[col + 1]
+                    + below[col - 1]
+                    + below[col]
+                    + below[col + 1];
+                out[col] = if mark_floor && here[col] == 0 {
+                    GONE
+                } else {
+                    sum
+                };
+            }
+            std::mem::swap(&mut above, &mut here);
+            std::mem::swap(&mut here, &mut below);
+        }
+        counts
+    }
+
+    /// Removes accessible rolls in waves, as part 2 does, until none are
+    /// left, and returns how many were removed. `on_wave` sees the flat
+    /// indices removed by each wave, in order.
+    ///
+    /// Each wave is every roll that is accessible once the previous wave has
+    /// gone. Only the neighbours of removed rolls can become accessible, so
+    /// rather than rescanning the grid each wave keeps the neighbour counts
+    /// up to date and queues a roll for the next wave when its count drops
+    /// below [`ACCESS_LIMIT`].
+    pub fn remove_in_waves(&self, mut on_wave: impl FnMut(&[usize])) -> usize {
+        // Neighbour counts of the rolls still standing, GONE everywhere
+        // else; the waves hold indices into this padded table
+        let width = self.cols + 2;
+        let mut counts = self.padded_counts(true);
+        let offsets = [
+            0,
+            1,
+            2,
+            width,
+            width + 2,
+            2 * width,
+            2 * width + 1,
+            2 * width + 2,
+        ];
+        let unpad = |padded: usize| padded - width - 1 - 2 * (padded / width - 1);
+        let mut wave: Vec<usize> = self
+            .accessible()
+            .iter()
+            .map(|index| index + width + 1 + 2 * (index / self.cols))
+            .collect();
+        let mut flat = Vec::new();
+        let mut removed = 0;
+
+        while !wave.is_empty() {
+            flat.clear();
+            flat.extend(wave.iter().map(|&padded| unpad(padded)));
+            on_wave(&flat);
+            removed += wave.len();
+            for &padded in &wave {
+                counts[padded] = GONE;
+            }
+
+            let mut next = Vec::new();
+            for &padded in &wave {
+                // The neighbourhood's top-left corner
+                let corner = padded - width - 1;
+                for offset in offsets {
+                    // No branch for GONE cells: they take at most 8
+                    // decrements, which leaves them far above the limit
+                    let neighbour = &mut counts[corner + offset];
+                    *neighbour -= 1;
+                    // Exactly once per roll: the step from the limit to below it
+                    if *neighbour == ACCESS_LIMIT - 1 {
+                        next.push(corner + offset);
+                    }
+                }
+            }
+            wave = next;
+        }
+        removed
+    }
+
+    /// The grid as text: `x` for rolls in `marked`, `@` for other rolls and
+    /// `.` for floor.
+    pub fn render(&self, marked: &RollGrid) -> String {
+        self.render_with(marked, &Symbols::default())
+    }
+
+    /// [`RollGrid::render`] in the given symbols.
+    pub fn render_with(&self, marked: &RollGrid, symbols: &Symbols) -> String {
+        let mut text = String::with_capacity(self.rows * (self.cols + 1));
+        for row in 0..self.rows {
+            if row > 0 {
+                text.push('\n');
+            }
+            for col in 0..self.cols {
+                let index = row * self.cols + col;
+                text.push(if marked.contains(index) {
+                    symbols.removed
+                } else if self.contains(index) {
+                    symbols.roll
+                } else {
+                    symbols.floor
+                });
+            }
+        }
+        text
+    }
+}
+
+/// The positions of the set bits of a word, lowest first.
+struct BitIter(u64);
+
+impl Iterator for BitIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+        let bit = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(bit)
+    }
+}
+
+/// Which cells around a cell count as its neighbours.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Neighbourhood {
+    /// The 8 cells sharing an edge or a corner.
+    #[default]
+    Moore,
+    /// The 4 cells sharing an edge.
+    VonNeumann,
+    /// These (row, column) offsets, rows growing downwards. They need not be
+    /// symmetric: a cell counts the rolls at its own position plus each
+    /// offset.
+    Custom(Vec<(i32, i32)>),
+}
+
+/// Most offsets a custom neighbourhood may have, so that every count, and a
+/// marker for cells with no roll, fit in a byte.
+pub const MAX_NEIGHBOURS: usize = 254;
+
+impl Neighbourhood {
+    pub fn offsets(&self) -> Vec<(i32, i32)> {
+        match self {
+            Neighbourhood::Moore => (-1..=1)
+                .flat_map(|dr| (-1..=1).map(move |dc| (dr, dc)))
+                .filter(|&offset| offset != (0, 0))
+                .collect(),
+            Neighbourhood::VonNeumann => vec![(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Neighbourhood::Custom(offsets) => offsets.clone(),
+        }
+    }
+}
+
+/// How a neighbour count is compared with [`Rules::threshold`]. In a rules
+/// file these are written `"<"`, `"<="`, `"=="`, `"!="`, `">="` and `">"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum Comparison {
+    #[default]
+    #[serde(rename = "<")]
+    Less,
+    #[serde(rename = "<=")]
+    LessOrEqual,
+    #[serde(rename = "==")]
+    Equal,
+    #[serde(rename = "!=")]
+    NotEqual,
+    #[serde(rename = ">=")]
+    GreaterOrEqual,
+    #[serde(rename = ">")]
+    Greater,
+}
+
+impl Comparison {
+    pub fn holds(self, count: u8, threshold: u8) -> bool {
+        match self {
+            Comparison::Less => count < threshold,
+            Comparison::LessOrEqual => count <= threshold,
+            Comparison::Equal => count == threshold,
+            Comparison::NotEqual => count != threshold,
+            Comparison::GreaterOrEqual => count >= threshold,
+            Comparison::Greater => count > threshold,
+        }
+    }
+}
+
+/// The characters for rolls, floor and removed rolls in input and output.
+/// Input cells other than `roll` are floor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Symbols {
+    pub roll: char,
+    pub floor: char,
+    pub removed: char,
+}
+
+impl Default for Symbols {
+    fn default() -> Self {
+        Symbols {
+            roll: '@',
+            floor: '.',
+            removed: 'x',
+        }
+    }
+}
+
+/// When a roll is accessible, as a small cellular-automaton rule: it is if
+/// the number of rolls in its `neighbourhood` compares with `threshold` as
+/// `comparison` says. With `wrap` the grid is a torus and neighbourhoods
+/// running off one edge continue from the opposite one, skipping any offset
+/// that wraps back onto the cell itself; otherwise cells beyond the edge are
+/// floor.
+///
+/// The default is the puzzle's: fewer than 4 of the 8 Moore neighbours, no
+/// wrap, `@`/`.`/`x`. Rules can be read from JSON with
+/// [`Rules::from_json`], any field left out keeping its default:
+///
+/// ```json
+/// {"neighbourhood": {"custom": [[-2, 0], [2, 0], [0, -2], [0, 2]]},
+///  "comparison": "<=", "threshold": 1, "wrap": true,
+///  "symbols": {"roll": "#", "floor": ".", "removed": "o"}}
+/// ```
+///
+/// `"neighbourhood"` may also be `"moore"` or `"von-neumann"`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rules {
+    pub neighbourhood: Neighbourhood,
+    pub comparison: Comparison,
+    pub threshold: u8,
+    pub wrap: bool,
+    pub symbols: Symbols,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            neighbourhood: Neighbourhood::Moore,
+            comparison: Comparison::Less,
+            threshold: ACCESS_LIMIT,
+            wrap: false,
+            symbols: Symbols::default(),
+        }
+    }
+}
+
+impl Rules {
+    /// Parses and [validates](Rules::validate) a JSON rules file. Either
+    /// kind of failure is a parse error.
+    pub fn from_json(text: &str) -> Result<Rules, AocError> {
+        let rules: Rules =
+            serde_json::from_str(text).map_err(|e| AocError::parse(e.to_string()))?;
+        rules.validate().map_err(AocError::parse)?;
+        Ok(rules)
+    }
+
+    /// Checks that custom offsets are distinct, leave out `(0, 0)` and
+    /// number at most [`MAX_NEIGHBOURS`], and that the three symbols differ.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Neighbourhood::Custom(offsets) = &self.neighbourhood {
+            if offsets.len() > MAX_NEIGHBOURS {
+                return Err(format!(
+                    "a neighbourhood has at most {} offsets, got {}",
+                    MAX_NEIGHBOURS,
+                    offsets.len()
+                ));
+            }
+            if offsets.contains(&(0, 0)) {
+                return Err("a cell is not its own neighbour: drop the (0, 0) offset".to_string());
+            }
+            let distinct: HashSet<_> = offsets.iter().collect();
+            if distinct.len() != offsets.len() {
+                return Err("neighbourhood offsets must be distinct".to_string());
+            }
+        }
+        let Symbols {
+            roll,
+            floor,
+            removed,
+        } = self.symbols;
+        if roll == floor || roll == removed || floor == removed {
+            return Err(format!(
+                "roll, floor and removed symbols must differ, got '{}', '{}' and '{}'",
+                roll, floor, removed
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether a roll with `count` neighbouring rolls is accessible.
+    pub fn accepts(&self, count: u8) -> bool {
+        self.comparison.holds(count, self.threshold)
+    }
+
+    /// Whether the bit-parallel scan and padded worklist, which only know
+    /// the puzzle's rule, can stand in for the general engine.
+    fn is_puzzle(&self) -> bool {
+        self.neighbourhood == Neighbourhood::Moore
+            && self.comparison == Comparison::Less
+            && self.threshold == ACCESS_LIMIT
+            && !self.wrap
+    }
+}
+
+impl RollGrid {
+    /// Flat index of the cell `offset` away from (row, col): wrapped round
+    /// under `wrap`, `None` past the edge otherwise. Also `None` when the
+    /// offset wraps all the way back to (row, col) on a grid smaller than
+    /// the neighbourhood, since a cell is never its own neighbour.
+    fn step(&self, row: usize, col: usize, (dr, dc): (i32, i32), wrap: bool) -> Option<usize> {
+        let (rows, cols) = (self.rows as i64, self.cols as i64);
+        let (r, c) = (row as i64 + i64::from(dr), col as i64 + i64::from(dc));
+        let (r, c) = if wrap {
+            (r.rem_euclid(rows), c.rem_euclid(cols))
+        } else if (0..rows).contains(&r) && (0..cols).contains(&c) {
+            (r, c)
+        } else {
+            return None;
+        };
+        if (r, c) == (row as i64, col as i64) {
+            return None;
+        }
+        Some(r as usize * self.cols + c as usize)
+    }
+
+    /// [`RollGrid::neighbour_counts`] over the rules' neighbourhood and
+    /// edges.
+    pub fn neighbour_counts_with(&self, rules: &Rules) -> Vec<u8> {
+        if rules.neighbourhood == Neighbourhood::Moore && !rules.wrap {
+            return self.neighbour_counts();
+        }
+        self.scatter_counts(rules)
+    }
+
+    /// Neighbour counts by adding each roll to every cell that counts it.
+    fn scatter_counts(&self, rules: &Rules) -> Vec<u8> {
+        let offsets = rules.neighbourhood.offsets();
+        let mut counts = vec![0u8; self.rows * self.cols];
+        for roll in self.iter() {
+            let (row, col) = self.position(roll);
+            for &(dr, dc) in &offsets {
+                if let Some(cell) = self.step(row, col, (-dr, -dc), rules.wrap) {
+                    counts[cell] += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// The rolls the rules make accessible; [`RollGrid::accessible`] for the
+    /// puzzle's rule.
+    pub fn accessible_with(&self, rules: &Rules) -> RollGrid {
+        if rules.is_puzzle() {
+            return self.accessible();
+        }
+        let counts = self.neighbour_counts_with(rules);
+        let mut accessible = RollGrid::new(self.rows, self.cols);
+        for roll in self.iter().filter(|&roll| rules.accepts(counts[roll])) {
+            accessible.insert(roll);
+        }
+        accessible
+    }
+
+    /// [`RollGrid::remove_in_waves`] under any rules.
+    ///
+    /// Counts only ever fall, one at a time, so whatever the comparison a
+    /// roll starts to pass it at most once; that is when it is queued.
+    pub fn remove_in_waves_with(&self, rules: &Rules, mut on_wave: impl FnMut(&[usize])) -> usize {
+        if rules.is_puzzle() {
+            return self.remove_in_waves(on_wave);
+        }
+        let offsets = rules.neighbourhood.offsets();
+        let mut counts = self.neighbour_counts_with(rules);
+        for (index, count) in counts.iter_mut().enumerate() {
+            if !self.contains(index) {
+                *count = GONE;
+            }
+        }
+        let mut wave: Vec<usize> = self
+            .iter()
+            .filter(|&roll| rules.accepts(counts[roll]))
+            .collect();
+        let mut removed = 0;
+
+        while !wave.is_empty() {
+            on_wave(&wave);
+            removed += wave.len();
+            for &index in &wave {
+                counts[index] = GONE;
+            }
+
+            let mut next = Vec::new();
+            for &index in &wave {
+                let (row, col) = self.position(index);
+                for &(dr, dc) in &offsets {
+                    // The cells that count this one among their neighbours
+                    let Some(cell) = self.step(row, col, (-dr, -dc), rules.wrap) else {
+                        continue;
+                    };
+                    let before = counts[cell];
+                    if before == GONE {
+                        continue;
+                    }
+                    counts[cell] = before - 1;
+                    if !rules.accepts(before) && rules.accepts(before - 1) {
+                        next.push(cell);
+                    }
+                }
+            }
+            // `==` can stop passing again before the wave is over
+            next.retain(|&cell| rules.accepts(counts[cell]));
+            wave = next;
+        }
+        removed
+    }
+}
+
+/// Parse input into a 2D grid of characters
+pub fn parse_grid(input: &str) -> Vec<Vec<char>> {
+    input
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().collect())
+        .collect()
+}
+
+/// [`Day04`] that also records every removal wave of part 2 as a text grid
+/// under `"additional-info"` (`day04 --frames`).
+pub struct FramedDay04;
+
+impl Solution for FramedDay04 {
+    const DAY: u8 = 4;
+    type Input = RollGrid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Day04::parse(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<usize, AocError> {
+        Day04::part1(grid)
+    }
+
+    fn part2(grid: &Self::Input) -> Result<usize, AocError> {
+        Day04::part2(grid)
+    }
+
+    fn additional_info(grid: &Self::Input) -> Option<Value> {
+        Some(grid_info(grid, &Rules::default(), true))
+    }
+}
+
+/// The `"additional-info"` object: the grid with its accessible rolls
+/// marked, and with `frames` every removal wave as well (see
+/// [`FramedDay04`]), all in the rules' symbols.
+pub fn grid_info(grid: &RollGrid, rules: &Rules, frames: bool) -> Value {
+    let marked = grid.render_with(&grid.accessible_with(rules), &rules.symbols);
+    if !frames {
+        return json!({ "grid": marked });
+    }
+    let frames: Vec<Value> = grid
+        .removal_frames_with(rules)
+        .iter()
+        .enumerate()
+        .map(|(wave, frame)| {
+            json!({
+                "wave": wave,
+                "removed": frame.removed.count(),
+                "grid": frame.text_with(&rules.symbols),
+            })
+        })
+        .collect();
+    json!({ "grid": marked, "frames": frames })
+}
+
+/// The floor just before one removal wave, with the rolls that wave takes
+/// marked, as in the puzzle's worked example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// Every roll still standing, including the ones about to go.
+    pub standing: RollGrid,
+    /// The rolls this wave removes; empty for the initial state.
+    pub removed: RollGrid,
+}
+
+/// Colour index of a floor cell in [`Frame::pixels`] and [`PALETTE`].
+pub const FLOOR: u8 = 0;
+/// Colour index of a standing roll.
+pub const ROLL: u8 = 1;
+/// Colour index of a roll removed by the frame's wave.
+pub const REMOVED: u8 = 2;
+
+/// RGB triples for [`FLOOR`], [`ROLL`] and [`REMOVED`].
+pub const PALETTE: [u8; 9] = [24, 24, 32, 200, 200, 200, 230, 90, 40];
+
+impl RollGrid {
+    /// Every frame of part 2: the initial state (nothing removed), then one
+    /// per removal wave. Each frame holds two copies of the grid, so keep
+    /// this to grids of a sensible size.
+    pub fn removal_frames(&self) -> Vec<Frame> {
+        self.removal_frames_with(&Rules::default())
+    }
+
+    /// [`RollGrid::removal_frames`] under any rules.
+    pub fn removal_frames_with(&self, rules: &Rules) -> Vec<Frame> {
+        let mut frames = vec![Frame {
+            standing: self.clone(),
+            removed: RollGrid::new(self.rows, self.cols),
+        }];
+        let mut standing = self.clone();
+        self.remove_in_waves_with(rules, |wave| {
+            let mut removed = RollGrid::new(self.rows, self.cols);
+            for &index in wave {
+                removed.insert(index);
+            }
+            frames.push(Frame {
+                standing: standing.clone(),
+                removed,
+            });
+            for &index in wave {
+                standing.remove(index);
+            }
+        });
+        frames
+    }
+}
+
+impl Frame {
+    /// `x` for removed rolls, `@` for the rest and `.` for floor.
+    pub fn text(&self) -> String {
+        self.standing.render(&self.removed)
+    }
+
+    /// [`Frame::text`] in the given symbols.
+    pub fn text_with(&self, symbols: &Symbols) -> String {
+        self.standing.render_with(&self.removed, symbols)
+    }
+
+    /// Image size in pixels with each cell drawn `scale` pixels square.
+    pub fn size(&self, scale: usize) -> (usize, usize) {
+        (self.standing.cols * scale, self.standing.rows * scale)
+    }
+
+    /// One [`PALETTE`] index per pixel, row by row.
+    pub fn pixels(&self, scale: usize) -> Vec<u8> {
+        let (width, height) = self.size(scale);
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in 0..self.standing.rows {
+            let line: Vec<u8> = (0..self.standing.cols)
+                .flat_map(|col| {
+                    let index = row * self.standing.cols + col;
+                    let colour = if self.removed.contains(index) {
+                        REMOVED
+                    } else if self.standing.contains(index) {
+                        ROLL
+                    } else {
+                        FLOOR
+                    };
+                    std::iter::repeat_n(colour, scale)
+                })
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        pixels
+    }
+}
+
+/// Image formats for single frames (`day04 --frames-dir`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageFormat {
+    /// Binary PPM (`P6`), readable by nearly anything and trivial to write.
+    Ppm,
+    #[default]
+    Png,
+}
+
+impl ImageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+impl std::str::FromStr for ImageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ppm" => Ok(ImageFormat::Ppm),
+            "png" => Ok(ImageFormat::Png),
+            other => Err(format!(
+                "unknown image format '{}' (expected ppm or png)",
+                other
+            )),
+        }
+    }
+}
+
+/// Writes one frame as an image, each cell `scale` pixels square.
+pub fn write_frame_image(
+    out: &mut impl Write,
+    frame: &Frame,
+    format: ImageFormat,
+    scale: usize,
+) -> io::Result<()> {
+    let (width, height) = frame.size(scale);
+    let pixels = frame.pixels(scale);
+    match format {
+        ImageFormat::Ppm => {
+            write!(out, "P6\n{} {}\n255\n", width, height)?;
+            let rgb: Vec<u8> = pixels
+                .iter()
+                .flat_map(|&colour| {
+                    let start = usize::from(colour) * 3;
+                    PALETTE[start..start + 3].iter().copied()
+                })
+                .collect();
+            out.write_all(&rgb)
+        }
+        ImageFormat::Png => {
+            let mut encoder = png::Encoder::new(out, dimension(width)?, dimension(height)?);
+            encoder.set_color(png::ColorType::Indexed);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_palette(&PALETTE[..]);
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&pixels)?;
+            writer.finish()?;
+            Ok(())
+        }
+    }
+}
+
+/// Writes every frame as one looping animated GIF, each shown for
+/// `delay_cs` hundredths of a second. GIF limits the image to 65535 pixels
+/// a side.
+pub fn write_gif(
+    out: &mut impl Write,
+    frames: &[Frame],
+    scale: usize,
+    delay_cs: u16,
+) -> io::Result<()> {
+    let Some(first) = frames.first() else {
+        return Ok(());
+    };
+    let (width, height) = first.size(scale);
+    let too_big = || {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{}x{} pixels is too large for a GIF", width, height),
+        )
+    };
+    let width = u16::try_from(width).map_err(|_| too_big())?;
+    let height = u16::try_from(height).map_err(|_| too_big())?;
+
+    let mut encoder = gif::Encoder::new(out, width, height, &PALETTE).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    for frame in frames {
+        let mut image = gif::Frame::from_indexed_pixels(width, height, frame.pixels(scale), None);
+        image.delay = delay_cs;
+        encoder.write_frame(&image).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+fn dimension(pixels: usize) -> io::Result<u32> {
+    u32::try_from(pixels).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} pixels is too large for an image", pixels),
+        )
+    })
+}
+
+/// Count adjacent rolls ('@' symbols) in all 8 directions
+/// Directions: N, S, E, W, NE, NW, SE, SW
+///
+/// One cell at a time; [`RollGrid::neighbour_counts`] does the whole grid.
+pub fn count_adjacent_rolls(grid: &[Vec<char>], row: usize, col: usize) -> usize {
+    let directions = [
+        (-1, 0),  // N
+        (1, 0),   // S
+        (0, 1),   // E
+        (0, -1),  // W
+        (-1, 1),  // NE
+        (-1, -1), // NW
+        (1, 1),   // SE
+        (1, -1),  // SW
+    ];
+
+    let rows = grid.len() as i32;
+    let cols = grid[0].len() as i32;
+    let mut count = 0;
+
+    for (dr, dc) in directions.iter() {
+        let new_row = row as i32 + dr;
+        let new_col = col as i32 + dc;
+
+        // Check bounds
+        if new_row >= 0 && new_row < rows && new_col >= 0 && new_col < cols {
+            let r = new_row as usize;
+            let c = new_col as usize;
+            if grid[r][c] == '@' {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+/// Identify which rolls are accessible (adjacent_count < 4)
+/// Returns a set of (row, col) positions that are accessible
+///
+/// Other neighbourhoods and thresholds: [`RollGrid::accessible_with`].
+pub fn identify_accessible_rolls(grid: &[Vec<char>]) -> Vec<(usize, usize)> {
+    let mut accessible = Vec::new();
+
+    for (row, line) in grid.iter().enumerate() {
+        for (col, &cell) in line.iter().enumerate() {
+            if cell == '@' {
+                let adjacent_count = count_adjacent_rolls(grid, row, col);
+                if adjacent_count < 4 {
+                    accessible.push((row, col));
+                }
+            }
+        }
+    }
+
+    accessible
+}
+
+/// Create grid visualization
+/// 'x' for accessible rolls, '@' for inaccessible rolls, '.' for empty spaces
+pub fn create_visualization(grid: &[Vec<char>], accessible: &[(usize, usize)]) -> String {
+    let accessible: HashSet<(usize, usize)> = accessible.iter().copied().collect();
+    let mut result = Vec::new();
+
+    for (row, line) in grid.iter().enumerate() {
+        let mut row_chars = Vec::new();
+        for (col, &cell) in line.iter().enumerate() {
+            if accessible.contains(&(row, col)) {
+                row_chars.push('x');
+            } else {
+                row_chars.push(cell);
+            }
+        }
+        result.push(row_chars.iter().collect::<String>());
+    }
+
+    result.join("\n")
+}
+
+/// Remove rolls from the grid at specified positions
+/// Returns a new grid with removed positions replaced by '.'
+pub fn remove_rolls(grid: &[Vec<char>], positions: &[(usize, usize)]) -> Vec<Vec<char>> {
+    let mut new_grid = grid.to_vec();
+
+    for &(row, col) in positions {
+        new_grid[row][col] = '.';
+    }
+
+    new_grid
+}
+
+/// Solve Part 2: Iteratively remove accessible rolls
+/// Returns total count of removed rolls across all iterations
+pub fn solve_part2(input: &str) -> usize {
+    RollGrid::parse(input).remove_in_waves(|_| {})
+}
+
+/// [`solve_part2`], also returning the initial state and every removal wave
+/// as [`Frame`]s for export
+pub fn solve_part2_with_frames(input: &str) -> (usize, Vec<Frame>) {
+    let frames = RollGrid::parse(input).removal_frames();
+    let removed = frames.iter().map(|frame| frame.removed.count()).sum();
+    (removed, frames)
+}
+
+/// Repeatedly removes every accessible roll until none remain accessible
+/// Returns total count of removed rolls across all iterations
+pub fn count_removable_rolls(grid: &[Vec<char>]) -> usize {
+    RollGrid::from_chars(grid).remove_in_waves(|_| {})
+}
+
+/// Solve the puzzle: count accessible rolls and create visualization
+pub fn solve(input: &str) -> (usize, String) {
+    let grid = RollGrid::parse(input);
+    let accessible = grid.accessible();
+    (accessible.count(), grid.render(&accessible))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_grid() {
+        let input = "..@@\n@@@.\n";
+        let grid = parse_grid(input);
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid[0], vec!['.', '.', '@', '@']);
+        assert_eq!(grid[1], vec!['@', '@', '@', '.']);
+    }
+
+    #[test]
+    fn test_count_adjacent_corner_cell() {
+        // Corner cell at (0,0) with one adjacent roll
+        let grid = vec![
+            vec!['@', '@', '.'],
+            vec!['@', '.', '.'],
+            vec!['.', '.', '.'],
+        ];
+        let count = count_adjacent_rolls(&grid, 0, 0);
+        // Adjacent to: (0,1) and (1,0) = 2 rolls
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_count_adjacent_edge_cell() {
+        // Edge cell at (0,1) - top edge, middle
+        let grid = vec![
+            vec!['@', '@', '@'],
+            vec!['@', '@', '@'],
+            vec!['.', '.', '.'],
+        ];
+        let count = count_adjacent_rolls(&grid, 0, 1);
+        // Adjacent to: (0,0), (0,2), (1,0), (1,1), (1,2) = 5 rolls
+        assert_eq!(count, 5);
+    }
+
+    #[test]
+    fn test_count_adjacent_interior_cell() {
+        // Interior cell at (1,1) with all 8 neighbors
+        let grid = vec![
+            vec!['@', '@', '@'],
+            vec!['@', '@', '@'],
+            vec!['@', '@', '@'],
+        ];
+        let count = count_adjacent_rolls(&grid, 1, 1);
+        // All 8 adjacent positions have rolls
+        assert_eq!(count, 8);
+    }
+
+    #[test]
+    fn test_accessibility_rule() {
+        // Test that adjacent_count < 4 means accessible
+        let grid = vec![
+            vec!['@', '@', '.'],
+            vec!['@', '@', '@'],
+            vec!['.', '@', '.'],
+        ];
+        // Cell at (0,0) has 3 adjacent rolls: (0,1), (1,0), (1,1) -> accessible
+        // Cell at (1,1) has 5 adjacent rolls -> not accessible
+        let accessible = identify_accessible_rolls(&grid);
+        assert!(accessible.contains(&(0, 0)));
+        assert!(!accessible.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn test_sample_input() {
+        let input = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+        let (count, _) = solve(input);
+        assert_eq!(count, 13);
+    }
+
+    #[test]
+    fn test_visualization_format() {
+        let input = "..@@\n@@@.\n";
+        let (_, viz) = solve(input);
+        // Check that visualization contains 'x' for accessible and '@' for inaccessible
+        assert!(viz.contains('x') || viz.contains('@'));
+        assert!(viz.contains('\n'));
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let input = "";
+        let (count, viz) = solve(input);
+        assert_eq!(count, 0);
+        assert_eq!(viz, "");
+    }
+
+    // Part 2 Tests
+
+    #[test]
+    fn test_part2_sample_input_total() {
+        let input = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+        let total = solve_part2(input);
+        assert_eq!(total, 43);
+    }
+
+    #[test]
+    fn test_part2_preserves_part1() {
+        let input = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+        let (part1, _) = solve(input);
+        assert_eq!(part1, 13);
+    }
+
+    #[test]
+    fn test_part2_empty_grid() {
+        let input = "";
+        let total = solve_part2(input);
+        assert_eq!(total, 0);
+    }
+
+    #[test]
+    fn test_remove_rolls() {
+        let grid = vec![vec!['@', '@', '.'], vec!['@', '.', '.']];
+        let positions = vec![(0, 0), (0, 1)];
+        let new_grid = remove_rolls(&grid, &positions);
+
+        assert_eq!(new_grid[0][0], '.');
+        assert_eq!(new_grid[0][1], '.');
+        assert_eq!(new_grid[1][0], '@'); // Unchanged
+    }
+
+    #[test]
+    fn test_part2_single_iteration() {
+        // Grid where all rolls are accessible (< 4 adjacent)
+        let input = "@..\n.@.\n..@";
+        let total = solve_part2(input);
+        // All 3 rolls are accessible in first iteration (0-1 neighbors each)
+        assert_eq!(total, 3);
+    }
+
+    #[test]
+    fn test_part2_no_accessible_rolls() {
+        // Grid where center roll is protected by exactly 4 neighbors
+        // Make a 5x5 grid with a protected roll at (2,2) that has exactly 4 adjacent
+        let input = ".....\n.@@@.\n.@@@.\n.@@@.\n.....";
+        let _grid = parse_grid(input);
+        // Center roll at (2,2) should have exactly 8 neighbors
+        // Corner rolls like (1,1) have 3 neighbors, edges have 5
+        // After first iteration, some outer rolls are removed
+        // Verify iteration eventually stops
+        let total = solve_part2(input);
+        // Should remove rolls but stop when none are accessible
+        assert!(total > 0);
+        assert!(total <= 9); // Max 9 rolls in grid
+    }
+
+    // Packed grid
+
+    /// The batch removal `count_removable_rolls` used before the worklist:
+    /// rescan the whole grid, remove everything accessible, repeat.
+    fn rescan_waves(grid: &[Vec<char>]) -> Vec<usize> {
+        let mut grid = grid.to_vec();
+        let mut waves = Vec::new();
+        loop {
+            let accessible = identify_accessible_rolls(&grid);
+            if accessible.is_empty() {
+                return waves;
+            }
+            waves.push(accessible.len());
+            grid = remove_rolls(&grid, &accessible);
+        }
+    }
+
+    #[test]
+    fn test_roll_grid_across_word_boundaries() {
+        // Rolls either side of the 64-column word boundary see each other
+        let mut line = ".".repeat(130);
+        line.replace_range(62..67, "@@@@@");
+        let input = format!("{line}\n{line}\n{line}");
+        let grid = RollGrid::parse(&input);
+        assert_eq!((grid.rows(), grid.cols(), grid.count()), (3, 130, 15));
+
+        let counts = grid.neighbour_counts();
+        assert_eq!(counts[130 + 63], 8);
+        assert_eq!(counts[130 + 64], 8);
+        assert_eq!(counts[62], 3);
+        assert_eq!(counts[61], 2);
+
+        // Only the four corners have fewer than 4 neighbours
+        let accessible: Vec<usize> = grid.accessible().iter().collect();
+        assert_eq!(accessible, vec![62, 66, 2 * 130 + 62, 2 * 130 + 66]);
+    }
+
+    #[test]
+    fn test_remove_in_waves_reports_each_wave() {
+        let input = ".....\n.@@@.\n.@@@.\n.@@@.\n.....";
+        let grid = RollGrid::parse(input);
+        let mut waves = Vec::new();
+        let removed = grid.remove_in_waves(|wave| {
+            let mut wave: Vec<_> = wave.iter().map(|&i| grid.position(i)).collect();
+            wave.sort();
+            waves.push(wave);
+        });
+        // Corners first, then the edges and the centre they exposed
+        assert_eq!(removed, 9);
+        assert_eq!(waves[0], vec![(1, 1), (1, 3), (3, 1), (3, 3)]);
+        assert_eq!(waves.iter().map(Vec::len).sum::<usize>(), 9);
+    }
+
+    #[test]
+    fn test_render_matches_visualization() {
+        let input = "..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@";
+        let grid = parse_grid(input);
+        let packed = RollGrid::from_chars(&grid);
+        assert_eq!(
+            packed.render(&packed.accessible()),
+            create_visualization(&grid, &identify_accessible_rolls(&grid))
+        );
+    }
+
+    // Frames
+
+    const SAMPLE: &str = "..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.";
+
+    #[test]
+    fn test_removal_frames_follow_the_worked_example() {
+        let (removed, frames) = solve_part2_with_frames(SAMPLE);
+        assert_eq!(removed, 43);
+        let counts: Vec<usize> = frames.iter().map(|f| f.removed.count()).collect();
+        assert_eq!(counts, vec![0, 13, 12, 7, 5, 2, 1, 1, 1, 1]);
+
+        // The initial state, then "Remove 13 rolls of paper"
+        assert_eq!(frames[0].text(), SAMPLE);
+        assert!(frames[1].text().starts_with("..xx.xx@x.\nx@@.@.@.@@\n"));
+        // Each frame starts from what the previous one left
+        assert_eq!(frames[2].standing.count(), 71 - 13);
+    }
+
+    #[test]
+    fn test_framed_day04_reports_text_frames() {
+        let grid = FramedDay04::parse(SAMPLE).unwrap();
+        let info = FramedDay04::additional_info(&grid).unwrap();
+        assert_eq!(info["frames"].as_array().unwrap().len(), 10);
+        assert_eq!(info["frames"][1]["removed"], 13);
+        assert_eq!(info["grid"], Day04::additional_info(&grid).unwrap()["grid"]);
+    }
+
+    #[test]
+    fn test_frame_images() {
+        let frames = RollGrid::parse("@@.\n@..").removal_frames();
+        let frame = &frames[1];
+        assert_eq!(frame.size(2), (6, 4));
+        assert_eq!(
+            frame.pixels(1),
+            vec![REMOVED, REMOVED, FLOOR, REMOVED, FLOOR, FLOOR]
+        );
+
+        let mut ppm = Vec::new();
+        write_frame_image(&mut ppm, frame, ImageFormat::Ppm, 2).unwrap();
+        let header = b"P6\n6 4\n255\n";
+        assert!(ppm.starts_with(header));
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+
+        let mut png_bytes = Vec::new();
+        write_frame_image(&mut png_bytes, frame, ImageFormat::Png, 2).unwrap();
+        let mut reader = png::Decoder::new(io::Cursor::new(png_bytes))
+            .read_info()
+            .unwrap();
+        let mut decoded = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut decoded).unwrap();
+        assert_eq!((info.width, info.height), (6, 4));
+        assert_eq!(&decoded[..info.buffer_size()], &frame.pixels(2)[..]);
+    }
+
+    #[test]
+    fn test_write_gif() {
+        let frames = RollGrid::parse(SAMPLE).removal_frames();
+        let mut bytes = Vec::new();
+        write_gif(&mut bytes, &frames, 3, 20).unwrap();
+
+        let mut decoder = gif::DecodeOptions::new();
+        decoder.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = decoder.read_info(io::Cursor::new(bytes)).unwrap();
+        let mut decoded = 0;
+        while let Some(image) = decoder.read_next_frame().unwrap() {
+            assert_eq!((image.width, image.height, image.delay), (30, 30, 20));
+            assert_eq!(image.buffer.as_ref(), &frames[decoded].pixels(3)[..]);
+            decoded += 1;
+        }
+        assert_eq!(decoded, frames.len());
+
+        let mut too_wide = Vec::new();
+        let wide = RollGrid::parse(&"@".repeat(70_000)).removal_frames();
+        assert!(write_gif(&mut too_wide, &wide, 1, 20).is_err());
+    }
+
+    // Rules
+
+    /// Part 2 under `rules` by brute force: count every standing roll's
+    /// neighbours afresh each wave. Each wave comes back sorted.
+    fn naive_waves(grid: &RollGrid, rules: &Rules) -> Vec<Vec<usize>> {
+        let (rows, cols) = (grid.rows() as i64, grid.cols() as i64);
+        let offsets = rules.neighbourhood.offsets();
+        let mut standing = grid.clone();
+        let mut waves = Vec::new();
+        loop {
+            let wave: Vec<usize> = standing
+                .iter()
+                .filter(|&roll| {
+                    let (row, col) = (roll as i64 / cols, roll as i64 % cols);
+                    let neighbours = offsets
+                        .iter()
+                        .filter(|&&(dr, dc)| {
+                            let (mut r, mut c) = (row + i64::from(dr), col + i64::from(dc));
+                            if rules.wrap {
+                                r = r.rem_euclid(rows);
+                                c = c.rem_euclid(cols);
+                            }
+                            (0..rows).contains(&r)
+                                && (0..cols).contains(&c)
+                                && (r, c) != (row, col)
+                                && standing.contains((r * cols + c) as usize)
+                        })
+                        .count();
+                    rules.accepts(neighbours as u8)
+                })
+                .collect();
+            if wave.is_empty() {
+                return waves;
+            }
+            for &roll in &wave {
+                standing.remove(roll);
+            }
+            waves.push(wave);
+        }
+    }
+
+    fn sorted_waves(grid: &RollGrid, rules: &Rules) -> Vec<Vec<usize>> {
+        let mut waves = Vec::new();
+        grid.remove_in_waves_with(rules, |wave| {
+            let mut wave = wave.to_vec();
+            wave.sort_unstable();
+            waves.push(wave);
+        });
+        waves
+    }
+
+    #[test]
+    fn test_rules_from_json() {
+        assert_eq!(Rules::from_json("{}"), Ok(Rules::default()));
+
+        let rules = Rules::from_json(
+            r##"{"neighbourhood": {"custom": [[-2, 0], [2, 0]]}, "comparison": ">=",
+                "threshold": 1, "wrap": true, "symbols": {"roll": "#"}}"##,
+        )
+        .unwrap();
+        assert_eq!(
+            rules.neighbourhood,
+            Neighbourhood::Custom(vec![(-2, 0), (2, 0)])
+        );
+        assert_eq!(rules.comparison, Comparison::GreaterOrEqual);
+        assert!(rules.wrap);
+        assert_eq!(
+            rules.symbols,
+            Symbols {
+                roll: '#',
+                ..Symbols::default()
+            }
+        );
+
+        let von_neumann = Rules::from_json(r#"{"neighbourhood": "von-neumann"}"#).unwrap();
+        assert_eq!(von_neumann.neighbourhood.offsets().len(), 4);
+
+        // Malformed and invalid files are parse errors, exit code 4
+        for bad in [r#"{"wrap": tru"#, r#"{"threshold": 300}"#] {
+            assert_eq!(Rules::from_json(bad).unwrap_err().exit_code(), 4);
+        }
+        assert!(Rules::from_json(r#"{"threshhold": 3}"#).is_err());
+        assert!(Rules::from_json(r#"{"comparison": "=<"}"#).is_err());
+        assert!(Rules::from_json(r#"{"neighbourhood": {"custom": [[0, 0]]}}"#).is_err());
+        assert!(Rules::from_json(r#"{"neighbourhood": {"custom": [[1, 0], [1, 0]]}}"#).is_err());
+        assert!(Rules::from_json(r#"{"symbols": {"removed": "."}}"#).is_err());
+    }
+
+    #[test]
+    fn test_von_neumann_and_symbols() {
+        let rules = Rules {
+            neighbourhood: Neighbourhood::VonNeumann,
+            comparison: Comparison::LessOrEqual,
+            threshold: 2,
+            symbols: Symbols {
+                roll: '#',
+                floor: ' ',
+                removed: 'o',
+            },
+            ..Rules::default()
+        };
+        // A plus sign: only the centre has more than 2 edge neighbours, and
+        // it goes once the arms have
+        let grid = RollGrid::parse_with(" # \n###\n # \n", &rules.symbols);
+        assert_eq!((grid.rows(), grid.cols(), grid.count()), (3, 3, 5));
+        let accessible = grid.accessible_with(&rules);
+        assert_eq!(
+            grid.render_with(&accessible, &rules.symbols),
+            " o \no#o\n o "
+        );
+        assert_eq!(sorted_waves(&grid, &rules), vec![vec![1, 3, 5, 7], vec![4]]);
+
+        let frames = grid.removal_frames_with(&rules);
+        assert_eq!(frames[2].text_with(&rules.symbols), "   \n o \n   ");
+        assert_eq!(grid_info(&grid, &rules, false)["grid"], " o \no#o\n o ");
+    }
+
+    #[test]
+    fn test_wrap_makes_edges_neighbours() {
+        // On a torus every cell of a full 3x3 grid has 8 neighbours
+        let grid = RollGrid::parse("@@@\n@@@\n@@@");
+        let torus = Rules {
+            wrap: true,
+            ..Rules::default()
+        };
+        assert!(grid.neighbour_counts_with(&torus).iter().all(|&n| n == 8));
+        assert_eq!(grid.accessible_with(&torus).count(), 0);
+        assert_eq!(grid.accessible_with(&Rules::default()).count(), 4);
+
+        // Two columns apart on a 1x4 torus: (0, 0) and (0, 2) see each other twice over
+        let strip = RollGrid::parse("@.@.");
+        let wide = Rules {
+            neighbourhood: Neighbourhood::Custom(vec![(0, -2), (0, 2)]),
+            wrap: true,
+            ..Rules::default()
+        };
+        assert_eq!(strip.neighbour_counts_with(&wide), vec![2, 0, 2, 0]);
+    }
+
+    #[test]
+    fn test_wrap_never_counts_a_cell_as_its_own_neighbour() {
+        let torus = Rules {
+            wrap: true,
+            ..Rules::default()
+        };
+        // On a 1x1 torus every Moore offset wraps back onto the cell
+        let single = RollGrid::parse("@");
+        assert_eq!(single.neighbour_counts_with(&torus), vec![0]);
+        assert_eq!(sorted_waves(&single, &torus), vec![vec![0]]);
+
+        // On a 2x2 torus the 8 Moore offsets all reach other cells, some
+        // twice over, but offsets of 2 wrap back and only (0, 1) counts
+        let square = RollGrid::parse("@@\n@@");
+        assert_eq!(square.neighbour_counts_with(&torus), vec![8, 8, 8, 8]);
+        let reach_two = Rules {
+            neighbourhood: Neighbourhood::Custom(vec![(0, 2), (2, 0), (2, 2), (0, 1)]),
+            wrap: true,
+            ..Rules::default()
+        };
+        assert_eq!(square.neighbour_counts_with(&reach_two), vec![1, 1, 1, 1]);
+        assert_eq!(sorted_waves(&square, &reach_two), vec![vec![0, 1, 2, 3]]);
+        assert_eq!(
+            sorted_waves(&square, &reach_two),
+            naive_waves(&square, &reach_two)
+        );
+    }
+
+    #[test]
+    fn test_equal_rule_drops_rolls_that_overshoot() {
+        // Exactly 2 neighbours in a row of five: only the middle three at
+        // first; after that the ends have none and nothing else qualifies
+        let grid = RollGrid::parse("@@@@@");
+        let rules = Rules {
+            comparison: Comparison::Equal,
+            threshold: 2,
+            ..Rules::default()
+        };
+        assert_eq!(sorted_waves(&grid, &rules), vec![vec![1, 2, 3]]);
+        assert_eq!(sorted_waves(&grid, &rules), naive_waves(&grid, &rules));
+    }
+
+    use proptest::prelude::*;
+
+    fn arb_grid() -> impl Strategy<Value = Vec<Vec<char>>> {
+        (1usize..8, 1usize..140).prop_flat_map(|(rows, cols)| {
+            prop::collection::vec(prop::collection::vec(prop::bool::weighted(0.6), cols), rows)
+                .prop_map(|cells| {
+                    cells
+                        .into_iter()
+                        .map(|row| {
+                            row.into_iter()
+                                .map(|roll| if roll { '@' } else { '.' })
+                                .collect()
+                        })
+                        .collect()
+                })
+        })
+    }
+
+    fn arb_rules() -> impl Strategy<Value = Rules> {
+        let neighbourhood = prop_oneof![
+            Just(Neighbourhood::Moore),
+            Just(Neighbourhood::VonNeumann),
+            prop::collection::hash_set((-3i32..=3, -3i32..=3), 0..10).prop_map(|offsets| {
+                Neighbourhood::Custom(offsets.into_iter().filter(|&o| o != (0, 0)).collect())
+            }),
+        ];
+        let comparison = prop::sample::select(vec![
+            Comparison::Less,
+            Comparison::LessOrEqual,
+            Comparison::Equal,
+            Comparison::NotEqual,
+            Comparison::GreaterOrEqual,
+            Comparison::Greater,
+        ]);
+        (neighbourhood, comparison, 0u8..10, any::<bool>()).prop_map(
+            |(neighbourhood, comparison, threshold, wrap)| Rules {
+                neighbourhood,
+                comparison,
+                threshold,
+                wrap,
+                symbols: Symbols::default(),
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn prop_neighbour_counts_match_per_cell(grid in arb_grid()) {
+            let counts = RollGrid::from_chars(&grid).neighbour_counts();
+            let cols = grid[0].len();
+            for row in 0..grid.len() {
+                for col in 0..cols {
+                    prop_assert_eq!(
+                        usize::from(counts[row * cols + col]),
+                        count_adjacent_rolls(&grid, row, col)
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn prop_accessible_matches_per_cell(grid in arb_grid()) {
+            let packed = RollGrid::from_chars(&grid);
+            let accessible: Vec<_> = packed
+                .accessible()
+                .iter()
+                .map(|index| packed.position(index))
+                .collect();
+            prop_assert_eq!(accessible, identify_accessible_rolls(&grid));
+        }
+
+        #[test]
+        fn prop_general_engine_matches_puzzle_fast_path(grid in arb_grid()) {
+            // `<= 3` is the puzzle's rule but not spelled the way the fast path checks for
+            let grid = RollGrid::from_chars(&grid);
+            let rules = Rules {
+                comparison: Comparison::LessOrEqual,
+                threshold: ACCESS_LIMIT - 1,
+                ..Rules::default()
+            };
+            prop_assert_eq!(grid.accessible_with(&rules), grid.accessible());
+            prop_assert_eq!(sorted_waves(&grid, &rules), sorted_waves(&grid, &Rules::default()));
+        }
+
+        #[test]
+        fn prop_rules_match_brute_force(grid in arb_grid(), rules in arb_rules()) {
+            let grid = RollGrid::from_chars(&grid);
+            prop_assert_eq!(sorted_waves(&grid, &rules), naive_waves(&grid, &rules));
+        }
+
+        #[test]
+        fn prop_worklist_waves_match_rescanning(grid in arb_grid()) {
+            let mut waves = Vec::new();
+            RollGrid::from_chars(&grid).remove_in_waves(|wave| waves.push(wave.len()));
+            prop_assert_eq!(waves, rescan_waves(&grid));
+        }
+    }
+}
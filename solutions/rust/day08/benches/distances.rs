@@ -0,0 +1,47 @@
+//! Benchmarks for `compute_distances`, the O(n²) pairwise distance heap built
+//! while parsing. The real input has 1000 junction boxes.
+
+use std::collections::HashSet;
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use day08::{Coordinate, compute_distances, parse_grid};
+
+/// `n` distinct junction boxes spread pseudo-randomly over the same
+/// 0..100000 coordinate range as the real input.
+fn synthetic_boxes(n: usize) -> HashSet<Coordinate> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        ((state >> 33) % 100_000) as i32
+    };
+    let mut boxes = HashSet::with_capacity(n);
+    while boxes.len() < n {
+        boxes.insert(Coordinate::new(next(), next(), next()));
+    }
+    boxes
+}
+
+fn bench_compute_distances(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day08/compute_distances");
+    group.sample_size(10);
+
+    let sample = std::fs::read_to_string(aoc_core::input_path(8, true)).unwrap();
+    let sample = parse_grid(&sample).unwrap();
+    group.bench_function("sample", |b| {
+        b.iter(|| compute_distances(black_box(&sample)))
+    });
+
+    for n in [100, 250, 500, 1000] {
+        let boxes = synthetic_boxes(n);
+        group.bench_with_input(BenchmarkId::new("synthetic", n), &boxes, |b, boxes| {
+            b.iter(|| compute_distances(black_box(boxes)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_compute_distances);
+criterion_main!(benches);
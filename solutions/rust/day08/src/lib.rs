@@ -0,0 +1,627 @@
+use aoc_core::{AocError, Solution};
+use ordered_float::NotNan;
+use std::cmp::Ordering;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::Hash;
+use uuid::Uuid;
+
+/// Day 8: connect junction boxes closest-first; multiply the three largest circuit
+/// sizes (part 1) and the X coordinates of the final connection (part 2).
+pub struct Day08;
+
+/// Junction box positions together with every pairwise distance, closest first
+#[derive(Debug, Clone)]
+pub struct Playground {
+    pub boxes: HashSet<Coordinate>,
+    pub distances: BinaryHeap<Reverse<CoordinatDistance>>,
+}
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Input = Playground;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        let boxes = parse_grid(input)?;
+        let distances = compute_distances(&boxes);
+        Ok(Playground { boxes, distances })
+    }
+
+    fn part1(playground: &Self::Input) -> Result<u128, AocError> {
+        Ok(top_3_product(
+            playground,
+            connection_limit(playground.boxes.len()),
+        ))
+    }
+
+    fn part2(playground: &Self::Input) -> Result<u128, AocError> {
+        Ok(last_connection_product(playground))
+    }
+}
+
+/// Represents a 3d coordinate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Coordinate {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+impl Coordinate {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Coordinate { x, y, z }
+    }
+}
+impl fmt::Display for Coordinate {
+    // This trait requires the `fmt` method with this exact signature
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Use the write! macro to write the formatted output into the Formatter
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoordinatDistance {
+    pub distance: NotNan<f64>,
+    pub coord_a: Coordinate,
+    pub coord_b: Coordinate,
+}
+impl fmt::Display for CoordinatDistance {
+    // This trait requires the `fmt` method with this exact signature
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Use the write! macro to write the formatted output into the Formatter
+        write!(
+            f,
+            "({},{}:{:.0})",
+            self.coord_a.x, self.coord_b, self.distance
+        )
+    }
+}
+impl Ord for CoordinatDistance {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Use f64::total_cmp to create a total ordering
+        // Note: This makes it a max-heap by default
+        self.distance.total_cmp(&other.distance)
+    }
+}
+impl PartialOrd for CoordinatDistance {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CoordinateCircuit {
+    pub coordinate: Coordinate,
+    pub circuit_id: Option<Uuid>,
+}
+impl fmt::Display for CoordinateCircuit {
+    // This trait requires the `fmt` method with this exact signature
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Use the write! macro to write the formatted output into the Formatter
+        write!(
+            f,
+            "{}:{},{},{}",
+            self.circuit_id.unwrap_or(Uuid::nil()),
+            self.coordinate.x,
+            self.coordinate.y,
+            self.coordinate.z
+        )
+    }
+}
+
+/// Connects junction boxes closest-pair first, merging circuits as it goes.
+///
+/// Every pair popped from `distances` counts as a connection, even when both boxes
+/// are already in the same circuit. Stops after `max_connections` pairs (if given) or
+/// once all `box_count` boxes form a single circuit.
+///
+/// Returns the boxes that ended up in a circuit and the last pair that joined two
+/// separate circuits.
+pub fn create_connections(
+    distances: &mut BinaryHeap<Reverse<CoordinatDistance>>,
+    box_count: usize,
+    max_connections: Option<usize>,
+) -> (HashSet<CoordinateCircuit>, Option<(Coordinate, Coordinate)>) {
+    let mut circuits: HashMap<Coordinate, CoordinateCircuit> = HashMap::new();
+    let mut last_two: Option<(Coordinate, Coordinate)> = None;
+    let mut connections = 0;
+    // Every box starts out as its own circuit
+    let mut circuit_count = box_count;
+
+    while circuit_count > 1 && max_connections.is_none_or(|max| connections < max) {
+        let Some(current) = distances.pop() else {
+            break;
+        };
+        connections += 1;
+        let coord_a: Coordinate = current.0.coord_a;
+        let coord_b = current.0.coord_b;
+
+        match (circuits.get(&coord_a), circuits.get(&coord_b)) {
+            (Some(a_circuit), Some(b_circuit)) => {
+                if a_circuit.circuit_id == b_circuit.circuit_id {
+                    // Already in the same circuit, nothing happens
+                    continue;
+                }
+                // merge circuits
+                let a_id = a_circuit.circuit_id;
+                let b_id = b_circuit.circuit_id;
+                for entry in circuits.values_mut() {
+                    if entry.circuit_id == a_id {
+                        entry.circuit_id = b_id;
+                    }
+                }
+            }
+            (Some(existing_circuit), None) => {
+                // coord_a is already in a circuit, add coord_b to that circuit
+                let new_circuit = CoordinateCircuit {
+                    coordinate: coord_b,
+                    circuit_id: existing_circuit.circuit_id,
+                };
+                circuits.insert(new_circuit.coordinate, new_circuit);
+            }
+            (None, Some(existing_circuit)) => {
+                // coord_b is already in a circuit, add coord_a to that circuit
+                let new_circuit = CoordinateCircuit {
+                    coordinate: coord_a,
+                    circuit_id: existing_circuit.circuit_id,
+                };
+                circuits.insert(new_circuit.coordinate, new_circuit);
+            }
+            (None, None) => {
+                let new_circuit_id = Uuid::new_v4();
+                let circuit_a = CoordinateCircuit {
+                    coordinate: coord_a,
+                    circuit_id: Some(new_circuit_id),
+                };
+                let circuit_b = CoordinateCircuit {
+                    coordinate: coord_b,
+                    circuit_id: Some(new_circuit_id),
+                };
+                circuits.insert(circuit_a.coordinate, circuit_a);
+                circuits.insert(circuit_b.coordinate, circuit_b);
+            }
+        }
+        circuit_count -= 1;
+        last_two = Some((coord_a, coord_b));
+    }
+
+    (circuits.values().cloned().collect(), last_two)
+}
+
+/// Number of closest pairs connected for part 1: the 20-box example connects
+/// 10 pairs, the real input connects 1000.
+pub fn connection_limit(box_count: usize) -> usize {
+    if box_count <= 20 { 10 } else { 1000 }
+}
+
+/// Product of the three largest circuit sizes after the first `max_connections` connections
+pub fn top_3_product(playground: &Playground, max_connections: usize) -> u128 {
+    let mut distances = playground.distances.clone();
+    let (circuits, _) = create_connections(
+        &mut distances,
+        playground.boxes.len(),
+        Some(max_connections),
+    );
+    let grouped_map = group_coordinates_by_circuit(&circuits);
+    let circuit_vec = circuits_decending(&grouped_map);
+    let top_3 = pick_top_3_circuits(&circuit_vec);
+    calc_top_3_product(&top_3)
+}
+
+/// Product of the X coordinates of the last two boxes that had to be connected
+/// to join everything into a single circuit
+pub fn last_connection_product(playground: &Playground) -> u128 {
+    let mut distances = playground.distances.clone();
+    let (_, last_two) = create_connections(&mut distances, playground.boxes.len(), None);
+    match last_two {
+        Some((second_last, last)) => second_last.x as u128 * last.x as u128,
+        None => 1,
+    }
+}
+
+pub fn compute_distances(grid: &HashSet<Coordinate>) -> BinaryHeap<Reverse<CoordinatDistance>> {
+    let mut distances: BinaryHeap<Reverse<CoordinatDistance>> = BinaryHeap::new();
+    let mut seen_pairs: HashSet<(Coordinate, Coordinate)> = HashSet::new();
+    for coord in grid.clone() {
+        for coord2 in grid.clone() {
+            if coord != coord2 {
+                let x_comp = coord.x as f64 - coord2.x as f64;
+                let y_comp = coord.y as f64 - coord2.y as f64;
+                let z_comp = coord.z as f64 - coord2.z as f64;
+
+                let x_squared = x_comp * x_comp;
+                let y_squared = y_comp * y_comp;
+                let z_squared = z_comp * z_comp;
+
+                let dist = (x_squared + y_squared + z_squared).sqrt();
+                let dist_nan = NotNan::new(dist).unwrap();
+                let coord_dist = CoordinatDistance {
+                    distance: dist_nan,
+                    coord_a: coord,
+                    coord_b: coord2,
+                };
+
+                if !seen_pairs.contains(&(coord, coord2)) && !seen_pairs.contains(&(coord2, coord))
+                {
+                    distances.push(Reverse(coord_dist));
+                    seen_pairs.insert((coord, coord2));
+                    seen_pairs.insert((coord2, coord));
+                } else {
+                    continue;
+                }
+            }
+        }
+    }
+    distances
+}
+
+pub fn group_coordinates_by_circuit(
+    circuits: &HashSet<CoordinateCircuit>,
+) -> HashMap<Uuid, HashSet<Coordinate>> {
+    let mut grouped_map: HashMap<Uuid, HashSet<Coordinate>> = HashMap::new();
+
+    for box_circuit in circuits {
+        // Use entry() to check if the ID already exists in the grouped_map.
+        // If it does, get the mutable reference to the Vec.
+        // If it doesn't, insert a new empty Vec.
+        if let Some(cicuit_id) = box_circuit.circuit_id {
+            grouped_map
+                .entry(cicuit_id)
+                .or_default()
+                .insert(box_circuit.coordinate);
+        } else {
+            grouped_map.insert(
+                Uuid::new_v4(),
+                [box_circuit.coordinate].iter().cloned().collect(),
+            );
+        }
+    }
+
+    log::trace!("Final Grouped Map: {:?}", grouped_map);
+
+    grouped_map
+}
+
+pub fn circuits_decending(grouped_map: &HashMap<Uuid, HashSet<Coordinate>>) -> Vec<usize> {
+    let mut circuit_vec: Vec<usize> = grouped_map.values().map(|v| v.len()).collect();
+
+    circuit_vec.sort();
+    circuit_vec.reverse();
+
+    log::debug!("Final Circuits: {:?}", circuit_vec);
+    circuit_vec
+}
+
+pub fn pick_top_3_circuits(circuit_vec: &[usize]) -> Vec<usize> {
+    let top_3 = circuit_vec.iter().take(3).cloned().collect();
+    log::debug!("Top 3 Circuits: {:?}", top_3);
+    top_3
+}
+
+pub fn calc_top_3_product(top_3: &[usize]) -> u128 {
+    top_3.iter().fold(1_u128, |acc, x| acc * (*x as u128))
+}
+
+pub fn solve(input: &str) -> Result<(u128, u128), AocError> {
+    let grid = parse_grid(input)?;
+
+    log::info!("Parsed grid successfully.");
+
+    let distances = compute_distances(&grid);
+    log::info!("computed distances");
+
+    let playground = Playground {
+        boxes: grid,
+        distances,
+    };
+
+    let mult_top_three = top_3_product(&playground, connection_limit(playground.boxes.len()));
+    log::info!("calc product of top 3");
+
+    let two_result = last_connection_product(&playground);
+    log::info!("calc product of last connection");
+
+    Ok((mult_top_three, two_result))
+}
+
+/// Parse one "x,y,z" junction box position per line
+pub fn parse_grid(input: &str) -> Result<HashSet<Coordinate>, AocError> {
+    let mut grid = HashSet::new();
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let coords: Vec<i32> = line
+            .split(",")
+            .map(|s| s.trim().parse::<i32>())
+            .collect::<Result<_, _>>()
+            .map_err(|e| {
+                AocError::parse_at_line(index + 1, format!("Failed to parse integer: {}", e))
+            })?;
+        let [x, y, z] = coords[..] else {
+            return Err(AocError::parse_at_line(
+                index + 1,
+                format!("expected 3 coordinates, found {}", coords.len()),
+            ));
+        };
+        grid.insert(Coordinate::new(x, y, z));
+    }
+    Ok(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SAMPLE_INPUT: &str = "162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689";
+
+    static EXPECTED_DISTANCES_LEN: usize = 190;
+
+    // Part 1 tests
+
+    /// The two boxes of a connection, independent of the order they were stored in
+    fn pair_of(distance: &CoordinatDistance) -> HashSet<Coordinate> {
+        [distance.coord_a, distance.coord_b].into_iter().collect()
+    }
+
+    #[test]
+    fn test_create_first_ten_connections() {
+        let grid = parse_grid(SAMPLE_INPUT).unwrap();
+        let mut distances = compute_distances(&grid);
+        let (circuits, _) = create_connections(&mut distances, grid.len(), Some(10));
+
+        assert!(distances.len() < EXPECTED_DISTANCES_LEN); // Some distances should have been removed
+        assert_eq!(circuits.len(), 13); // Each coordinate should map to a circuit
+
+        for coord in &circuits {
+            println!("{}", coord);
+            assert!(coord.circuit_id.is_some()); // All circuits should have an ID
+        }
+        let grouped_by_circuit = group_coordinates_by_circuit(&circuits);
+        let mut counts_per_circuit: Vec<usize> = grouped_by_circuit
+            .values()
+            .map(|coords| coords.len())
+            .collect();
+        counts_per_circuit.sort();
+        counts_per_circuit.reverse();
+        println!("Counts per circuit: {:?}", counts_per_circuit);
+
+        let boxes_in_circuits: HashSet<Coordinate> = grouped_by_circuit
+            .values()
+            .flat_map(|s| s.iter())
+            .copied()
+            .collect();
+        assert_eq!(
+            circuits
+                .iter()
+                .map(|x| &x.coordinate)
+                .collect::<HashSet<_>>(),
+            boxes_in_circuits.iter().collect::<HashSet<_>>()
+        ); // All coordinates should be accounted for
+
+        assert_eq!(grouped_by_circuit.keys().len(), 4);
+        assert_eq!(counts_per_circuit, vec![5, 4, 2, 2]);
+    }
+
+    #[test]
+    fn test_min_distance_sample() {
+        let grid = parse_grid(SAMPLE_INPUT).unwrap();
+        let mut distances = compute_distances(&grid);
+
+        let expected = [
+            (
+                Coordinate::new(162, 817, 812),
+                Coordinate::new(425, 690, 689),
+            ),
+            (
+                Coordinate::new(162, 817, 812),
+                Coordinate::new(431, 825, 988),
+            ),
+            (
+                Coordinate::new(906, 360, 560),
+                Coordinate::new(805, 96, 715),
+            ),
+        ];
+        for (a, b) in expected {
+            let closest = distances.pop().unwrap().0;
+            assert_eq!(pair_of(&closest), [a, b].into_iter().collect());
+        }
+    }
+
+    #[test]
+    fn test_compute_distances() {
+        let grid = parse_grid(SAMPLE_INPUT).unwrap();
+        let distances = compute_distances(&grid);
+        assert_eq!(distances.len(), EXPECTED_DISTANCES_LEN);
+
+        // No box is paired with itself and every pair appears once
+        let mut pairs = HashSet::new();
+        for Reverse(distance) in distances.iter() {
+            assert_ne!(distance.coord_a, distance.coord_b);
+            let mut pair = [distance.coord_a, distance.coord_b];
+            pair.sort();
+            assert!(pairs.insert(pair));
+        }
+    }
+
+    #[test]
+    fn test_min_distance() {
+        let c1 = Coordinate::new(0, 0, 0);
+        let c2 = Coordinate::new(1, 1, 1);
+        let c3 = Coordinate::new(3, 3, 3);
+        let grid: HashSet<Coordinate> = [c1, c2, c3].into_iter().collect();
+
+        let mut distances = compute_distances(&grid);
+        let min_pair = distances.peek().unwrap().0;
+        assert_eq!(pair_of(&min_pair), [c1, c2].into_iter().collect());
+
+        // The first connection joins c1 and c2, the second has to bring in c3
+        let (circuits, last_two) = create_connections(&mut distances, grid.len(), None);
+        assert_eq!(circuits.len(), 3);
+        let (a, b) = last_two.unwrap();
+        assert_eq!(
+            [a, b].into_iter().collect::<HashSet<_>>(),
+            [c2, c3].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn test_sample_answers() {
+        let (part1, part2) = solve(SAMPLE_INPUT).unwrap();
+        assert_eq!(part1, 40);
+        assert_eq!(part2, 25272);
+    }
+
+    #[test]
+    fn test_parse_grid_errors() {
+        assert_eq!(
+            parse_grid("1,2,3\n4,5\n"),
+            Err(AocError::parse_at_line(
+                2,
+                "expected 3 coordinates, found 2"
+            ))
+        );
+        assert!(matches!(
+            parse_grid("1,2,3\n4,five,6\n"),
+            Err(AocError::Parse { line: Some(2), .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let grid = parse_grid(SAMPLE_INPUT).unwrap();
+        assert_eq!(grid.len(), 20);
+
+        let expected = vec![
+            Coordinate {
+                x: 162,
+                y: 817,
+                z: 812,
+            },
+            Coordinate {
+                x: 57,
+                y: 618,
+                z: 57,
+            },
+            Coordinate {
+                x: 906,
+                y: 360,
+                z: 560,
+            },
+            Coordinate {
+                x: 592,
+                y: 479,
+                z: 940,
+            },
+            Coordinate {
+                x: 352,
+                y: 342,
+                z: 300,
+            },
+            Coordinate {
+                x: 466,
+                y: 668,
+                z: 158,
+            },
+            Coordinate {
+                x: 542,
+                y: 29,
+                z: 236,
+            },
+            Coordinate {
+                x: 431,
+                y: 825,
+                z: 988,
+            },
+            Coordinate {
+                x: 739,
+                y: 650,
+                z: 466,
+            },
+            Coordinate {
+                x: 52,
+                y: 470,
+                z: 668,
+            },
+            Coordinate {
+                x: 216,
+                y: 146,
+                z: 977,
+            },
+            Coordinate {
+                x: 819,
+                y: 987,
+                z: 18,
+            },
+            Coordinate {
+                x: 117,
+                y: 168,
+                z: 530,
+            },
+            Coordinate {
+                x: 805,
+                y: 96,
+                z: 715,
+            },
+            Coordinate {
+                x: 346,
+                y: 949,
+                z: 466,
+            },
+            Coordinate {
+                x: 970,
+                y: 615,
+                z: 88,
+            },
+            Coordinate {
+                x: 941,
+                y: 993,
+                z: 340,
+            },
+            Coordinate {
+                x: 862,
+                y: 61,
+                z: 35,
+            },
+            Coordinate {
+                x: 984,
+                y: 92,
+                z: 344,
+            },
+            Coordinate {
+                x: 425,
+                y: 690,
+                z: 689,
+            },
+        ]
+        .iter()
+        .copied()
+        .collect();
+
+        assert_eq!(grid, expected);
+    }
+}
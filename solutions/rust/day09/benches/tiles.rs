@@ -0,0 +1,62 @@
+//! Benchmarks for part 2's hot paths: building the prefix sums of the tile
+//! grid and the parallel scan over every pair of red tiles.
+
+use std::collections::HashSet;
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use day09::{Point, TileGrid, parse_points};
+
+/// Red tiles at the corners of a staircase polygon with `steps` steps of
+/// `step` tiles each: `2 * steps + 2` tiles on a `steps * step` square grid.
+/// Only consecutive corners share a row or column, so the green boundary is
+/// exactly the polygon's outline.
+fn staircase(steps: i32, step: i32) -> HashSet<Point> {
+    let mut points = HashSet::new();
+    points.insert(Point::new(0, 0));
+    for i in 0..steps {
+        points.insert(Point::new((i + 1) * step, i * step));
+        points.insert(Point::new((i + 1) * step, (i + 1) * step));
+    }
+    points.insert(Point::new(0, steps * step));
+    points
+}
+
+fn inputs() -> Vec<(String, HashSet<Point>)> {
+    let sample = std::fs::read_to_string(aoc_core::input_path(9, true)).unwrap();
+    let mut inputs = vec![("sample".to_string(), parse_points(&sample).unwrap())];
+    for (steps, step) in [(25, 8), (100, 10), (250, 8)] {
+        inputs.push((
+            format!("staircase-{}x{}", steps, step),
+            staircase(steps, step),
+        ));
+    }
+    inputs
+}
+
+fn bench_prefix_sums(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day09/build_prefix_sum");
+    for (name, points) in inputs() {
+        let tiles = TileGrid::new(&points);
+        group.bench_with_input(BenchmarkId::from_parameter(name), &tiles, |b, tiles| {
+            b.iter(|| black_box(tiles).prefix_sums())
+        });
+    }
+    group.finish();
+}
+
+fn bench_max_area(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day09/max_area_scan");
+    group.sample_size(20);
+    for (name, points) in inputs() {
+        let tiles = TileGrid::new(&points);
+        let sums = tiles.prefix_sums();
+        group.bench_with_input(BenchmarkId::from_parameter(name), &tiles, |b, tiles| {
+            b.iter(|| tiles.max_area(black_box(&sums)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_prefix_sums, bench_max_area);
+criterion_main!(benches);
@@ -0,0 +1,86 @@
+//! Benchmarks for `solve_counts_exact`, the branch-and-bound search used when
+//! the ILP solver gives up.
+
+use std::hint::black_box;
+use std::time::Duration;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use day10::{Machine, parse_problem, solve_counts_exact};
+
+/// Generous enough that every benchmarked machine is solved exactly.
+const NODE_LIMIT: usize = 50_000_000;
+const TIME_LIMIT: Duration = Duration::from_secs(30);
+
+/// A solvable machine line with `counters` counters and `buttons` buttons.
+/// Button `j` always wires counter `j % counters` (so every counter can be
+/// reached) plus up to two more; the goal is the total of pressing each
+/// button a pseudo-random 0..=`max_presses` times.
+fn synthetic_line(seed: u64, counters: usize, buttons: usize, max_presses: u64) -> String {
+    let mut state = seed;
+    let mut next = move |bound: u64| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % bound
+    };
+
+    let mut goal = vec![0u64; counters];
+    let mut wiring = Vec::with_capacity(buttons);
+    for j in 0..buttons {
+        let mut rows = vec![j % counters];
+        for _ in 0..next(3) {
+            rows.push(next(counters as u64) as usize);
+        }
+        rows.sort_unstable();
+        rows.dedup();
+        let presses = next(max_presses + 1);
+        for &r in &rows {
+            goal[r] += presses;
+        }
+        let rows: Vec<String> = rows.iter().map(|r| r.to_string()).collect();
+        wiring.push(format!("({})", rows.join(",")));
+    }
+    let goal: Vec<String> = goal.iter().map(|g| g.to_string()).collect();
+    format!(
+        "[{}] {} {{{}}}",
+        ".".repeat(counters),
+        wiring.join(" "),
+        goal.join(",")
+    )
+}
+
+fn machines() -> Vec<(String, Machine)> {
+    let sample = std::fs::read_to_string(aoc_core::input_path(10, true)).unwrap();
+    let mut machines: Vec<(String, Machine)> = parse_problem(&sample)
+        .unwrap()
+        .machines
+        .into_iter()
+        .enumerate()
+        .map(|(i, m)| (format!("sample-{}", i), m))
+        .collect();
+    for (counters, buttons, max_presses) in [(4, 6, 5), (6, 8, 8), (8, 10, 10)] {
+        let line = synthetic_line(0x9e37_79b9_7f4a_7c15, counters, buttons, max_presses);
+        let machine = parse_problem(&line).unwrap().machines.remove(0);
+        machines.push((format!("synthetic-{}x{}", counters, buttons), machine));
+    }
+    machines
+}
+
+fn bench_solve_counts_exact(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day10/solve_counts_exact");
+    group.sample_size(10);
+    for (name, machine) in machines() {
+        assert!(
+            solve_counts_exact(&machine, NODE_LIMIT, Some(TIME_LIMIT)).is_some(),
+            "{} should be solvable within the limits",
+            name
+        );
+        group.bench_with_input(BenchmarkId::from_parameter(name), &machine, |b, m| {
+            b.iter(|| solve_counts_exact(black_box(m), NODE_LIMIT, Some(TIME_LIMIT)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_solve_counts_exact);
+criterion_main!(benches);
@@ -0,0 +1,771 @@
+// Link the CBC solver built by cbc-src so no system-wide CBC install is needed.
+extern crate cbc_src;
+
+use aoc_core::AocError;
+use good_lp::solvers::coin_cbc;
+use good_lp::{IntoAffineExpression, Solution as _, SolverModel, constraint, variable, variables};
+use std::cmp::min;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Day 10: minimum button presses to configure each machine. Part 2 matches the
+/// joltage requirements exactly; part 1 (indicator lights) is not implemented.
+pub struct Day10;
+
+impl aoc_core::Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Problem;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_problem(input)
+    }
+
+    fn part1(_problem: &Self::Input) -> Result<usize, AocError> {
+        Err(AocError::UnsupportedPart { part: 1 })
+    }
+
+    fn part2(problem: &Self::Input) -> Result<usize, AocError> {
+        let limits = SolverLimits::from_env();
+        let mut total = 0;
+        for (i, machine) in problem.machines.iter().enumerate() {
+            total += solve_machine(machine, &limits).map_err(|e| match e {
+                AocError::Infeasible { message } => {
+                    AocError::infeasible(format!("machine {}: {}", i, message))
+                }
+                AocError::Timeout { message } => {
+                    AocError::timeout(format!("machine {}: {}", i, message))
+                }
+                other => other,
+            })?;
+        }
+        Ok(total)
+    }
+}
+
+/// Per-machine search limits, read from `PER_MACHINE_TIMEOUT_SECS` (default 600)
+/// and `PER_MACHINE_NODE_LIMIT` (default 200_000_000).
+#[derive(Clone, Debug)]
+pub struct SolverLimits {
+    pub timeout_secs: u64,
+    pub node_limit: usize,
+}
+
+impl SolverLimits {
+    pub fn from_env() -> Self {
+        let timeout_secs: u64 = std::env::var("PER_MACHINE_TIMEOUT_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(600);
+        let node_limit: usize = std::env::var("PER_MACHINE_NODE_LIMIT")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(200_000_000usize);
+        Self {
+            timeout_secs,
+            node_limit,
+        }
+    }
+}
+
+/// Try the ILP solver first (with conservative per-variable bounds), falling back to
+/// the exact DFS search if it fails. The error says whether the DFS proved there is
+/// no solution or gave up at a limit.
+pub fn solve_machine(machine: &Machine, limits: &SolverLimits) -> Result<usize, AocError> {
+    match solve_with_good_lp(machine, Some(limits.timeout_secs)) {
+        Some(total) => Ok(total),
+        // ILP returned None (infeasible / timeout / solver error) — fall back
+        None => exact_search(
+            machine,
+            limits.node_limit,
+            Some(Duration::from_secs(limits.timeout_secs)),
+        ),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ButtonWiringSchematic {
+    pub data: Vec<usize>,
+}
+impl ButtonWiringSchematic {
+    fn new(data: Vec<usize>) -> Self {
+        Self { data }
+    }
+}
+impl fmt::Display for ButtonWiringSchematic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "({})",
+            self.data
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct JoltageRequirement {
+    pub data: Vec<u16>,
+}
+impl JoltageRequirement {
+    fn new(data: Vec<u16>) -> Self {
+        Self { data }
+    }
+}
+impl fmt::Display for JoltageRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{{}}}",
+            self.data
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct IndicatorLightDiagram {
+    pub data: Vec<bool>,
+}
+impl IndicatorLightDiagram {
+    // fn new(size: usize) -> Self {
+    //     Self {
+    //         data: vec![false; size],
+    //     }
+    // }
+}
+impl fmt::Display for IndicatorLightDiagram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}]",
+            self.data
+                .iter()
+                .map(|b| if *b { "#" } else { "." })
+                .collect::<Vec<_>>()
+                .join("")
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Machine {
+    pub indicator_light_diag: IndicatorLightDiagram,
+    pub button_wiring_schemas: Vec<ButtonWiringSchematic>,
+    pub joltage_reqs: JoltageRequirement,
+    pub jotage_max_limit: u16,
+}
+impl Machine {
+    fn new(
+        ind: IndicatorLightDiagram,
+        schemas: Vec<ButtonWiringSchematic>,
+        jol: JoltageRequirement,
+    ) -> Self {
+        let max_limit = jol.data.iter().copied().max().unwrap_or(0);
+        Self {
+            indicator_light_diag: ind,
+            button_wiring_schemas: schemas,
+            joltage_reqs: jol,
+            jotage_max_limit: max_limit,
+        }
+    }
+}
+impl fmt::Display for Machine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.indicator_light_diag,
+            self.button_wiring_schemas
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .join(" "),
+            self.joltage_reqs
+        )
+    }
+}
+
+pub struct Problem {
+    pub machines: Vec<Machine>,
+}
+impl Problem {
+    fn new(m: Vec<Machine>) -> Self {
+        Self { machines: m }
+    }
+}
+
+pub fn parse_problem(input: &str) -> Result<Problem, AocError> {
+    let mut machines = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let line_no = index + 1;
+        let elems: Vec<&str> = line.split_whitespace().collect();
+        let ind_chars = elems[0].replace("[", "").replace("]", "");
+        let ind: Vec<bool> = ind_chars
+            .chars()
+            .map(|c| match c {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err(AocError::parse_at_line(
+                    line_no,
+                    format!("bad indicator light '{}'", c),
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+        let indicator = IndicatorLightDiagram { data: ind };
+        let schemas: Vec<ButtonWiringSchematic> = elems
+            .iter()
+            .skip(1)
+            .filter(|s| s.starts_with("("))
+            .map(|s| {
+                let data = s
+                    .replace("(", "")
+                    .replace(")", "")
+                    .split(',')
+                    .map(|x| {
+                        x.parse::<usize>().map_err(|e| {
+                            AocError::parse_at_line(
+                                line_no,
+                                format!("bad button index '{}': {}", x, e),
+                            )
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(ButtonWiringSchematic::new(data))
+            })
+            .collect::<Result<_, AocError>>()?;
+        let jol_raw = elems[elems.len() - 1].replace("{", "").replace("}", "");
+        let jol: Vec<u16> = jol_raw
+            .split(',')
+            .map(|c| {
+                c.parse().map_err(|e| {
+                    AocError::parse_at_line(line_no, format!("bad joltage '{}': {}", c, e))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        machines.push(Machine::new(
+            indicator,
+            schemas,
+            JoltageRequirement::new(jol),
+        ));
+    }
+    Ok(Problem::new(machines))
+}
+
+// greedy solver to get an initial upper bound (simple)
+pub fn greedy_upper_bound(machine: &Machine, max_steps: usize) -> Option<usize> {
+    let rows = machine.joltage_reqs.data.len();
+    let goal: Vec<usize> = machine
+        .joltage_reqs
+        .data
+        .iter()
+        .map(|&v| v as usize)
+        .collect();
+    let mut cur = vec![0usize; rows];
+    if cur == goal {
+        return Some(0);
+    }
+    for step in 0..max_steps {
+        let mut best_score: isize = -1;
+        let mut best_idx: Option<usize> = None;
+        for (si, schema) in machine.button_wiring_schemas.iter().enumerate() {
+            let mut feasible = true;
+            let mut score: isize = 0;
+            for &r in &schema.data {
+                if cur[r] >= goal[r] {
+                    feasible = false;
+                    break;
+                }
+                score += (goal[r] - cur[r]) as isize;
+            }
+            if !feasible {
+                continue;
+            }
+            if score > best_score {
+                best_score = score;
+                best_idx = Some(si);
+            }
+        }
+        let si = best_idx?;
+        for &r in &machine.button_wiring_schemas[si].data {
+            cur[r] += 1;
+        }
+        if cur == goal {
+            return Some(step + 1);
+        }
+    }
+    None
+}
+
+// exact integer multiset solver (branch & bound). returns minimal total presses if found within node_limit/time_limit.
+pub fn solve_counts_exact(
+    machine: &Machine,
+    node_limit: usize,
+    time_limit: Option<Duration>,
+) -> Option<usize> {
+    exact_search(machine, node_limit, time_limit).ok()
+}
+
+// same search, but reports whether it failed because no solution exists or because a limit was hit
+fn exact_search(
+    machine: &Machine,
+    node_limit: usize,
+    time_limit: Option<Duration>,
+) -> Result<usize, AocError> {
+    let start = Instant::now();
+    let _rows = machine.joltage_reqs.data.len();
+    let cols = machine.button_wiring_schemas.len();
+    let goal: Vec<usize> = machine
+        .joltage_reqs
+        .data
+        .iter()
+        .map(|&v| v as usize)
+        .collect();
+
+    // quick infeasible check
+    for (r, &need) in goal.iter().enumerate() {
+        if need > 0 {
+            let mut covered = false;
+            for s in &machine.button_wiring_schemas {
+                if s.data.contains(&r) {
+                    covered = true;
+                    break;
+                }
+            }
+            if !covered {
+                return Err(AocError::infeasible(format!(
+                    "no button is wired to counter {}",
+                    r
+                )));
+            }
+        }
+    }
+
+    // Build column coverings and per-column upper bounds
+    let mut col_rows: Vec<Vec<usize>> = vec![Vec::new(); cols];
+    for (c, s) in machine.button_wiring_schemas.iter().enumerate() {
+        for &r in &s.data {
+            col_rows[c].push(r);
+        }
+    }
+    let mut ub: Vec<usize> = vec![0; cols];
+    for c in 0..cols {
+        if col_rows[c].is_empty() {
+            ub[c] = 0;
+            continue;
+        }
+        let mut m = usize::MAX;
+        for &r in &col_rows[c] {
+            m = min(m, goal[r]);
+        }
+        ub[c] = if m == usize::MAX { 0 } else { m };
+    }
+
+    // column order: prefer schema with larger coverage (heuristic)
+    let mut order: Vec<usize> = (0..cols).collect();
+    order.sort_by_key(|&c| usize::MAX - col_rows[c].len());
+
+    // initial best from greedy
+    let mut best_total = None;
+    if let Some(gub) = greedy_upper_bound(machine, 2000) {
+        best_total = Some(gub);
+    }
+
+    let mut nodes: usize = 0;
+    let mut rem = goal.clone();
+
+    // helper lower bound: ceil(sum(rem)/max_cover_remaining)
+    fn lower_bound(rem: &[usize], cols_remaining: &[usize], col_rows: &[Vec<usize>]) -> usize {
+        let sum_rem: usize = rem.iter().sum();
+        if sum_rem == 0 {
+            return 0;
+        }
+        let mut maxcov = 1usize;
+        for &c in cols_remaining {
+            maxcov = maxcov.max(col_rows[c].len());
+        }
+        if maxcov == 0 {
+            sum_rem
+        } else {
+            sum_rem.div_ceil(maxcov)
+        }
+    }
+
+    // recursion
+    #[allow(clippy::too_many_arguments)]
+    fn dfs(
+        idx: usize,
+        order: &Vec<usize>,
+        ub: &Vec<usize>,
+        col_rows: &Vec<Vec<usize>>,
+        rem: &mut Vec<usize>,
+        nodes: &mut usize,
+        node_limit: usize,
+        best_total: &mut Option<usize>,
+        cur_total: usize,
+        start: Instant,
+        time_limit: Option<Duration>,
+    ) {
+        *nodes += 1;
+        if *nodes > node_limit {
+            return;
+        }
+        if let Some(tl) = time_limit
+            && start.elapsed() > tl
+        {
+            return;
+        }
+        // goal?
+        if rem.iter().all(|&x| x == 0) {
+            match best_total {
+                Some(bt) => {
+                    if cur_total < *bt {
+                        *best_total = Some(cur_total);
+                    }
+                }
+                None => *best_total = Some(cur_total),
+            }
+            return;
+        }
+        if idx >= order.len() {
+            return;
+        }
+        // pruning with current best
+        if let Some(bt) = *best_total
+            && cur_total >= bt
+        {
+            return;
+        }
+        // lower bound estimate using remaining columns
+        let cols_remaining = &order[idx..];
+        let lb = lower_bound(rem, cols_remaining, col_rows);
+        if let Some(bt) = *best_total
+            && cur_total + lb >= bt
+        {
+            return;
+        }
+        // pick column
+        let c = order[idx];
+        // compute max feasible for this column given rem (min of rem[r] for r in column)
+        let mut max_feasible = 0usize;
+        if !col_rows[c].is_empty() {
+            max_feasible = col_rows[c].iter().map(|&r| rem[r]).min().unwrap_or(0);
+            max_feasible = min(max_feasible, ub[c]);
+        }
+        // Try larger k first (heuristic)
+        for k in (0..=max_feasible).rev() {
+            // quick cost pruning
+            let new_total = cur_total + k;
+            if let Some(bt) = *best_total
+                && new_total >= bt
+            {
+                continue;
+            }
+            // apply k
+            let mut applied = false;
+            if k > 0 {
+                for &r in &col_rows[c] {
+                    rem[r] = rem[r].saturating_sub(k);
+                }
+                applied = true;
+            }
+            dfs(
+                idx + 1,
+                order,
+                ub,
+                col_rows,
+                rem,
+                nodes,
+                node_limit,
+                best_total,
+                new_total,
+                start,
+                time_limit,
+            );
+            // undo
+            if applied {
+                for &r in &col_rows[c] {
+                    rem[r] += k;
+                }
+            }
+            // if we've found a perfect (lowest possible) solution of 0 we can stop early, but lower bound already handled
+        }
+    }
+
+    dfs(
+        0,
+        &order,
+        &ub,
+        &col_rows,
+        &mut rem,
+        &mut nodes,
+        node_limit,
+        &mut best_total,
+        0,
+        start,
+        time_limit,
+    );
+
+    match best_total {
+        Some(total) => Ok(total),
+        None if nodes > node_limit => Err(AocError::timeout(format!(
+            "search node limit of {} reached",
+            node_limit
+        ))),
+        None if time_limit.is_some_and(|tl| start.elapsed() > tl) => Err(AocError::timeout(
+            format!("time limit of {:?} reached", time_limit.unwrap_or_default()),
+        )),
+        None => Err(AocError::infeasible(
+            "no combination of button presses matches the joltage requirements",
+        )),
+    }
+}
+
+// ILP solver using good_lp + coin_cbc (good_lp 1.x style)
+pub fn solve_with_good_lp(machine: &Machine, _time_limit_secs: Option<u64>) -> Option<usize> {
+    // quick feasibility check
+    let cols = machine.button_wiring_schemas.len();
+    let goal_i32: Vec<i32> = machine
+        .joltage_reqs
+        .data
+        .iter()
+        .map(|&v| v as i32)
+        .collect();
+    // also prepare goal as usize for upper-bound computation
+    let goal_usize: Vec<usize> = machine
+        .joltage_reqs
+        .data
+        .iter()
+        .map(|&v| v as usize)
+        .collect();
+
+    for (r, &need) in goal_i32.iter().enumerate() {
+        if need > 0 {
+            let mut covered = false;
+            for s in &machine.button_wiring_schemas {
+                if s.data.contains(&r) {
+                    covered = true;
+                    break;
+                }
+            }
+            if !covered {
+                return None;
+            }
+        }
+    }
+
+    // build per-column rows and conservative per-variable upper bounds (min over covered rows)
+    let mut col_rows: Vec<Vec<usize>> = vec![Vec::new(); cols];
+    for (c, s) in machine.button_wiring_schemas.iter().enumerate() {
+        for &r in &s.data {
+            col_rows[c].push(r);
+        }
+    }
+    let mut ub: Vec<usize> = vec![0; cols];
+    for c in 0..cols {
+        if col_rows[c].is_empty() {
+            ub[c] = 0;
+            continue;
+        }
+        let mut m = usize::MAX;
+        for &r in &col_rows[c] {
+            m = min(m, goal_usize[r]);
+        }
+        ub[c] = if m == usize::MAX { 0 } else { m };
+    }
+
+    // variables with conservative upper bounds to aid the MILP solver
+    let mut vars = variables!();
+    let mut xs = Vec::with_capacity(cols);
+    for (j, &ub_j) in ub.iter().enumerate() {
+        let max_j = ub_j as i32; // good_lp accepts i32 for bounds
+        let v = vars.add(
+            variable()
+                .integer()
+                .min(0)
+                .max(max_j)
+                .name(format!("x{}", j)),
+        );
+        xs.push(v);
+    }
+
+    // Degenerate: no variables. Return 0 if all goals are zero, otherwise infeasible.
+    if xs.is_empty() {
+        if goal_i32.iter().all(|&g| g == 0) {
+            return Some(0);
+        } else {
+            return None;
+        }
+    }
+
+    // objective: minimize sum(x_j)
+    // start from an affine expression produced from the first variable
+    let mut obj = xs[0].into_expression();
+    for v in xs.iter().skip(1) {
+        obj += *v;
+    }
+
+    // attach solver (use default CbcSolver)
+
+    let mut problem = vars.minimise(obj).using(coin_cbc::coin_cbc);
+
+    // equality constraints: for each row r, sum_{c where A[r][c]=1} x_c == goal[r]
+    for (r, &goal_r) in goal_i32.iter().enumerate() {
+        let mut expr = None;
+        for (c, schema) in machine.button_wiring_schemas.iter().enumerate() {
+            if schema.data.contains(&r) {
+                expr = Some(match expr {
+                    Some(e) => e + xs[c],
+                    None => xs[c].into_expression(),
+                });
+            }
+        }
+        if let Some(e) = expr {
+            problem = problem.with(constraint!(e == goal_r));
+        }
+    }
+
+    // Solve (the test wrapper already enforces a wall-clock timeout). Return sum(x).
+    match problem.solve() {
+        Ok(sol) => {
+            let mut total: usize = 0;
+            for &x in &xs {
+                let val = sol.value(x);
+                let iv = val.round() as isize;
+                if iv < 0 {
+                    return None;
+                }
+                total += iv as usize;
+            }
+            Some(total)
+        }
+        Err(e) => {
+            // Log solver error for diagnostics; the caller falls back to DFS
+            log::info!("ILP solver error / status: {:?}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::path::PathBuf;
+
+    // fallback embedded sample if file missing
+    const EMBED_SAMPLE: &str = "\
+[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+";
+
+    #[test]
+    fn sample_minimum_presses() {
+        let input = std::fs::read_to_string(aoc_core::input_path(10, true))
+            .unwrap_or_else(|_| EMBED_SAMPLE.to_string());
+        let prob = parse_problem(&input).unwrap();
+        let mut total: usize = 0;
+        for (i, m) in prob.machines.iter().enumerate() {
+            let res = solve_counts_exact(m, 2_000_000, Some(Duration::from_secs(5)));
+            match res {
+                Some(d) => {
+                    total += d;
+                    println!("sample machine {} -> {}", i, d);
+                }
+                None => {
+                    panic!("sample solver failed or infeasible for machine {}", i);
+                }
+            }
+        }
+        // expected total for sample is 33
+        assert_eq!(total, 33, "sample total mismatch");
+    }
+
+    #[test]
+    fn parse_error_reports_line() {
+        let input = "[.#] (0) (1) {1,1}\n[.#] (0) (x) {1,1}\n";
+        assert!(matches!(
+            parse_problem(input),
+            Err(AocError::Parse { line: Some(2), .. })
+        ));
+    }
+
+    #[test]
+    fn unreachable_counter_is_infeasible() {
+        let prob = parse_problem("[.#] (0) {1,2}").unwrap();
+        let limits = SolverLimits {
+            timeout_secs: 5,
+            node_limit: 1_000,
+        };
+        assert!(matches!(
+            exact_search(&prob.machines[0], limits.node_limit, None),
+            Err(AocError::Infeasible { .. })
+        ));
+    }
+
+    #[test]
+    #[ignore]
+    fn real_input_run() {
+        let input_path = env::var("DAY10_INPUT")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| aoc_core::input_path(10, false));
+        let input = std::fs::read_to_string(&input_path).expect("failed to read input file");
+        let prob = parse_problem(&input).unwrap();
+
+        let timeout_secs: u64 = env::var("PER_MACHINE_TIMEOUT_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(600);
+        let node_limit: usize = env::var("PER_MACHINE_NODE_LIMIT")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(200_000_000usize);
+
+        println!(
+            "Running real-input solver with timeout={}s node_limit={}",
+            timeout_secs, node_limit
+        );
+
+        let mut total: usize = 0;
+        for (i, m) in prob.machines.iter().enumerate() {
+            println!(
+                "Running machine {} (rows={}, schemas={})",
+                i,
+                m.joltage_reqs.data.len(),
+                m.button_wiring_schemas.len()
+            );
+
+            // Try ILP first (with conservative per-variable bounds), fallback to DFS exact search.
+            let ilp_res = solve_with_good_lp(m, Some(timeout_secs));
+            let res = if let Some(d) = ilp_res {
+                println!("machine {}: ILP solution -> {}", i, d);
+                Some(d)
+            } else {
+                // ILP returned None (infeasible / timeout / solver error) — fall back
+
+                solve_counts_exact(m, node_limit, Some(Duration::from_secs(timeout_secs)))
+            };
+
+            match res {
+                Some(d) => {
+                    println!("machine {} -> {}", i, d);
+                    total += d;
+                }
+                None => println!("machine {}: no solution found (or infeasible/timeout)", i),
+            }
+        }
+        println!("Total presses (real input): {}", total);
+    }
+}
@@ -0,0 +1,46 @@
+//! Benchmarks for `solve_tiling_problem`, the exact-cover search run for every
+//! region.
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use day12::{parse_problem, parse_region, solve_tiling_problem};
+
+/// Regions of growing size that pack the sample shapes at roughly the same
+/// density, all of them solvable. Denser packings quickly become
+/// exponential, e.g. `24x12: 6 6 5 5 5 6` takes tens of seconds.
+const SCALED_REGIONS: &[&str] = &[
+    "6x6: 1 1 1 1 0 0",
+    "12x9: 2 2 2 2 2 2",
+    "18x12: 4 4 4 4 4 4",
+    "30x15: 8 8 8 8 8 8",
+    "40x20: 12 12 12 12 12 12",
+];
+
+fn bench_solve_tiling(c: &mut Criterion) {
+    let sample = std::fs::read_to_string(aoc_core::input_path(12, true)).unwrap();
+    let problem = parse_problem(&sample).unwrap();
+
+    // The sample's third region has no solution and takes minutes to rule out
+    let mut regions: Vec<(String, _)> = problem.regions[..2]
+        .iter()
+        .enumerate()
+        .map(|(i, region)| (format!("sample-{}", i), region.clone()))
+        .collect();
+    for line in SCALED_REGIONS {
+        let region = parse_region(0, line).unwrap();
+        regions.push((format!("{}x{}", region.width, region.height), region));
+    }
+
+    let mut group = c.benchmark_group("day12/solve_tiling_problem");
+    group.sample_size(10);
+    for (name, region) in regions {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &region, |b, region| {
+            b.iter(|| solve_tiling_problem(&problem.shapes, black_box(region)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_solve_tiling);
+criterion_main!(benches);
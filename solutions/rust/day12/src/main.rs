@@ -1,505 +1,17 @@
-use core::fmt;
-use std::env;
-use std::fs;
-use std::sync::mpsc::channel;
-use std::time::Duration;
+use aoc_core::Solution;
+use day12::{Day12, get_all_orientations, parse_problem};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let input_type = match args.get(1).map(|s| s.as_str()) {
-        Some("r") => "",
-        Some("s") => "-sample",
-        _ => "",
-    };
-    let input_path = &format!("../../../challenges/day12/input{}.txt", input_type);
-    let input = read_input(input_path);
-    let problem = parse_problem(&input);
-    println!("{:?}", problem);
+    let options = aoc_core::options(Day12::DAY);
+    let input = options.read_input(Day12::DAY);
+    let problem = parse_problem(&input).unwrap_or_else(|e| aoc_core::fail(Day12::DAY, e));
+    log::debug!("{:?}", problem);
     for s in &problem.shapes {
         let orients = get_all_orientations(s);
-        println!("Shape {} has {} orientations", s.index, orients.len());
+        log::debug!("Shape {} has {} orientations", s.index, orients.len());
         for o in &orients {
-            println!("  {:?}", o);
-        }
-    }
-    println!("Part 1: {}", part1(&problem));
-    // println!("Part 2: {}", part2(&problem));
-}
-
-#[derive(Debug, Clone)]
-struct Shape {
-    index: usize,
-    coords: Vec<(usize, usize)>,
-}
-impl fmt::Display for Shape {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "Shape {}:", self.index)?;
-        let mut grid = vec![vec!['.'; 3]; 3];
-        for &(r, c) in &self.coords {
-            grid[r][c] = '#';
-        }
-        for row in grid {
-            let line: String = row.into_iter().collect();
-            writeln!(f, "{}", line)?;
-        }
-        Ok(())
-    }
-}
-
-#[derive(Debug, Clone)]
-struct Region {
-    width: usize,
-    height: usize,
-    shape_conts: Vec<usize>,
-}
-
-#[derive(Debug, Clone)]
-struct Problem {
-    shapes: Vec<Shape>,
-    regions: Vec<Region>,
-}
-
-fn read_input(path: &str) -> String {
-    fs::read_to_string(path).expect("Failed to read input file")
-}
-fn parse_shape(shape_lines: &Vec<&&str>) -> Shape {
-    let index = shape_lines
-        .iter()
-        .take(1)
-        .map(|idx| idx.replace(":", "").parse::<usize>().unwrap())
-        .next()
-        .unwrap();
-    let coords = shape_lines
-        .iter()
-        .skip(1)
-        .enumerate()
-        .map(|(x, &line)| {
-            line.chars().enumerate().filter_map(
-                move |(y, c)| {
-                    if c == '#' { Some((x, y)) } else { None }
-                },
-            )
-        })
-        .flatten()
-        .collect::<Vec<_>>();
-    Shape { index, coords }
-}
-
-use std::collections::HashSet;
-
-// Normalizes a shape's coordinates so the top-left point is at (0,0) and sorts for consistent HashSet hashing
-fn normalize_shape(coords: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
-    let min_r = coords.iter().map(|&(r, _)| r).min().unwrap_or(0);
-    let min_c = coords.iter().map(|&(_, c)| c).min().unwrap_or(0);
-
-    let mut normalized: Vec<(usize, usize)> = coords
-        .into_iter()
-        .map(|(r, c)| (r - min_r, c - min_c))
-        .collect();
-    normalized.sort_unstable(); // Sort ensures the Vec<Vec<T>> is hashable/comparable
-    normalized
-}
-
-// Rotates coordinates 90 degrees clockwise within a 3x3 boundary and normalizes
-fn rotate_90_clockwise(coords: &[(usize, usize)]) -> Vec<(usize, usize)> {
-    // Rotate around the bounding square of size S = max(max_r, max_c) + 1
-    let max_r = coords.iter().map(|&(r, _)| r).max().unwrap_or(0);
-    let max_c = coords.iter().map(|&(_, c)| c).max().unwrap_or(0);
-    let s = std::cmp::max(max_r, max_c) + 1;
-    let rotated = coords
-        .iter()
-        .map(|&(r, c)| (c, s - 1 - r))
-        .collect::<Vec<_>>();
-    normalize_shape(rotated)
-}
-
-// Flips coordinates horizontally (mirrors across a vertical axis) and normalizes
-fn flip_horizontal(coords: &[(usize, usize)]) -> Vec<(usize, usize)> {
-    let max_r = coords.iter().map(|&(r, _)| r).max().unwrap_or(0);
-    let max_c = coords.iter().map(|&(_, c)| c).max().unwrap_or(0);
-    let s = std::cmp::max(max_r, max_c) + 1;
-    let flipped = coords
-        .iter()
-        .map(|&(r, c)| (r, s - 1 - c))
-        .collect::<Vec<_>>();
-    normalize_shape(flipped)
-}
-
-// Return unique normalized orientations (as raw coord Vecs) for a base shape
-fn get_all_orientations(initial_shape: &Shape) -> Vec<Vec<(usize, usize)>> {
-    let mut unique_orientations: HashSet<Vec<(usize, usize)>> = HashSet::new();
-
-    let base = normalize_shape(initial_shape.coords.clone());
-    let flipped = flip_horizontal(&initial_shape.coords);
-
-    for mut variant in vec![base, flipped] {
-        for _ in 0..4 {
-            unique_orientations.insert(normalize_shape(variant.clone()));
-            variant = rotate_90_clockwise(&variant);
-        }
-    }
-
-    unique_orientations.into_iter().collect()
-}
-
-fn is_placement_valid(
-    shape_coords: &Vec<(usize, usize)>,
-    top_left_r: usize,
-    top_left_c: usize,
-    region: &Region,
-) -> bool {
-    for &(sr, sc) in shape_coords {
-        let r = top_left_r + sr;
-        let c = top_left_c + sc;
-        if r >= region.height || c >= region.width {
-            return false; // Out of bounds
-        }
-    }
-    true
-}
-
-fn generate_dlx_options(base_shapes: &Vec<Shape>, region: &Region) -> (Vec<Vec<usize>>, usize) {
-    // Returns (options, num_primary_columns)
-    let mut options_matrix: Vec<Vec<usize>> = Vec::new();
-
-    let cell_count = region.width * region.height;
-
-    // For each shape type, get all unique orientations
-    let all_orients_per_shape: Vec<Vec<Vec<(usize, usize)>>> = base_shapes
-        .iter()
-        .map(|s| get_all_orientations(s))
-        .collect();
-
-    // Count total shape instances
-    let mut total_shape_instances = 0usize;
-    for &count in &region.shape_conts {
-        total_shape_instances += count;
-    }
-
-    // We'll place shape-instance columns first (0..total_shape_instances-1)
-    // and cells after that (offset = total_shape_instances)
-    let cell_offset = total_shape_instances;
-
-    let mut shape_instance_index = 0usize;
-    println!(
-        "total_shape_instances: {} cell_offset: {} cell_count: {}",
-        total_shape_instances, cell_offset, cell_count
-    );
-    let mut placements_per_instance: Vec<usize> = Vec::new();
-    for (shape_type_idx, orients) in all_orients_per_shape.iter().enumerate() {
-        let count_for_type = region.shape_conts[shape_type_idx];
-        for _instance in 0..count_for_type {
-            let mut this_instance_count = 0usize;
-            let instance_col = shape_instance_index; // primary column
-            for orient in orients {
-                for r in 0..region.height {
-                    for c in 0..region.width {
-                        if is_placement_valid(orient, r, c, region) {
-                            let mut current_option: Vec<usize> = Vec::new();
-                            // add the shape-instance primary column (1-based)
-                            current_option.push(instance_col + 1);
-                            // add covered cell secondary columns (offset, 1-based)
-                            for (sr, sc) in orient {
-                                let linear_index = (r + sr) * region.width + (c + sc);
-                                current_option.push(cell_offset + linear_index + 1);
-                            }
-                            current_option.sort_unstable();
-                            options_matrix.push(current_option);
-                            this_instance_count += 1;
-                        }
-                    }
-                }
-            }
-            placements_per_instance.push(this_instance_count);
-            shape_instance_index += 1;
-        }
-    }
-
-    // for (i, &count) in placements_per_instance.iter().enumerate() {
-    //     println!("shape-instance {} placements: {}", i, count);
-    // }
-
-    // Add 'blank' options so that each cell column can be covered once
-    // This allows cells to remain empty (covered by a blank) while preventing overlaps.
-    for linear_index in 0..cell_count {
-        options_matrix.push(vec![cell_offset + linear_index + 1]);
-    }
-
-    (options_matrix, total_shape_instances + cell_count)
-}
-
-use dlx_rs::Solver;
-
-fn solve_tiling_problem(base_shapes: &Vec<Shape>, region: &Region) -> bool {
-    // Avoid building the full options matrix in memory. Stream placements directly
-    // into the DLX solver to keep peak memory low.
-    let total_shape_instances: usize = region.shape_conts.iter().sum();
-    let cell_count = region.width * region.height;
-    let total_columns = total_shape_instances + cell_count;
-
-    // Quick area-based pruning: sum of shape cells must be <= region cells
-    let mut total_shape_cells: usize = 0;
-    for (shape_idx, &count) in region.shape_conts.iter().enumerate() {
-        let per_shape_cells = base_shapes[shape_idx].coords.len();
-        total_shape_cells += per_shape_cells * count;
-    }
-    if total_shape_cells > cell_count {
-        // impossible by area
-        println!(
-            "Pruned by area: need {} cells but region has {}",
-            total_shape_cells, cell_count
-        );
-        return false;
-    }
-
-    // Prepare orientations per shape once
-    let all_orients_per_shape: Vec<Vec<Vec<(usize, usize)>>> = base_shapes
-        .iter()
-        .map(|s| get_all_orientations(s))
-        .collect();
-
-    // Compute number of placements per shape-instance first to allow early rejection
-    let mut placements_per_instance: Vec<usize> = Vec::new();
-    for (shape_type_idx, orients) in all_orients_per_shape.iter().enumerate() {
-        let count_for_type = region.shape_conts[shape_type_idx];
-        for _ in 0..count_for_type {
-            let mut this_instance_count = 0usize;
-            for orient in orients {
-                for r in 0..region.height {
-                    for c in 0..region.width {
-                        if is_placement_valid(orient, r, c, region) {
-                            this_instance_count += 1;
-                        }
-                    }
-                }
-            }
-            placements_per_instance.push(this_instance_count);
-        }
-    }
-
-    // If any shape instance has zero valid placements, region is impossible
-    if let Some(i) = placements_per_instance.iter().position(|&cnt| cnt == 0) {
-        println!("Pruned: shape-instance {} has 0 placements", i);
-        return false;
-    }
-
-    // Order shape-instances by fewest placements first (heuristic to reduce branching)
-    let mut instance_indices: Vec<usize> = (0..placements_per_instance.len()).collect();
-    instance_indices.sort_by_key(|&i| placements_per_instance[i]);
-
-    // Build mapping from logical instance idx -> solver column index
-    let mut instance_to_col: Vec<usize> = vec![0; instance_indices.len()];
-    for (new_col, &inst_idx) in instance_indices.iter().enumerate() {
-        instance_to_col[inst_idx] = new_col;
-    }
-
-    let mut s = Solver::new(total_columns);
-
-    let cell_offset = total_shape_instances;
-    let mut opt_index: usize = 0;
-
-    // Now add options, but using remapped primary columns so the most constrained instances
-    // correspond to the earliest solver columns.
-    let mut logical_instance = 0usize;
-    for (shape_type_idx, orients) in all_orients_per_shape.iter().enumerate() {
-        let count_for_type = region.shape_conts[shape_type_idx];
-        for _instance in 0..count_for_type {
-            let logical_col = logical_instance; // logical instance index
-            let solver_col = instance_to_col[logical_col];
-            for orient in orients {
-                for r in 0..region.height {
-                    for c in 0..region.width {
-                        if is_placement_valid(orient, r, c, region) {
-                            let mut option: Vec<usize> = Vec::with_capacity(1 + orient.len());
-                            // primary column for this shape-instance (1-based for dlx-rs)
-                            option.push(solver_col + 1);
-                            for (sr, sc) in orient {
-                                let linear_index = (r + sr) * region.width + (c + sc);
-                                option.push(cell_offset + linear_index + 1);
-                            }
-                            option.sort_unstable();
-                            s.add_option(format!("opt_{}", opt_index), &option);
-                            opt_index += 1;
-                        }
-                    }
-                }
-            }
-            logical_instance += 1;
-        }
-    }
-
-    // blank options for each cell (these use the original cell columns; they don't refer to instances)
-    for linear_index in 0..cell_count {
-        let option = vec![cell_offset + linear_index + 1];
-        s.add_option(format!("blank_{}", linear_index), &option);
-        opt_index += 1;
-    }
-
-    println!("Total columns: {}", total_columns);
-    println!("Total options added: {}", opt_index);
-
-    let solution = s.solve();
-    solution.is_some()
-}
-
-fn parse_problem(input: &str) -> Problem {
-    let raw_lines = input
-        .lines()
-        .map(|line| line.trim())
-        .filter(|l| !l.is_empty())
-        .collect::<Vec<_>>();
-
-    let mut shapes: Vec<Shape> = Vec::new();
-    for shape_idx in 0..6 {
-        let shape_raw = raw_lines
-            .iter()
-            .skip(shape_idx * 4)
-            .take(4)
-            .collect::<Vec<_>>();
-        let shape = parse_shape(&shape_raw);
-        shapes.push(shape);
-    }
-
-    let regions = raw_lines
-        .iter()
-        .skip(24)
-        .map(|&line| {
-            let parts = line.split_whitespace().collect::<Vec<_>>();
-            let dims = parts[0]
-                .split('x')
-                .map(|d| d.replace(":", "").parse::<usize>().unwrap())
-                .collect::<Vec<_>>();
-            // Input format may be WxH or HxW; assume first is width then height (as used elsewhere)
-            // println!("dims: {:?}", dims);
-            let shape_conts = parts[1..]
-                .iter()
-                .map(|&s| s.parse::<usize>().unwrap())
-                .collect::<Vec<_>>();
-            Region {
-                width: dims[0],
-                height: dims[1],
-                shape_conts,
-            }
-        })
-        .collect::<Vec<_>>();
-
-    Problem { shapes, regions }
-}
-
-fn part1(problem: &Problem) -> i32 {
-    let shapes = &problem.shapes;
-
-    // Controlled parallelism: spawn a small worker pool that uses an Atomic index
-    // to distribute region indices to workers. This avoids cloning Receivers and
-    // bounds memory by limiting concurrent solver instances.
-    let parallelism: usize = std::env::var("REGION_PARALLELISM")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(2);
-
-    let region_count = problem.regions.len();
-    let regions = problem.regions.clone();
-    let (res_tx, res_rx) = std::sync::mpsc::channel::<(usize, bool)>();
-    let next_idx = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
-
-    for _ in 0..parallelism {
-        let shapes = shapes.clone();
-        let regions = regions.clone();
-        let res_tx = res_tx.clone();
-        let next_idx = next_idx.clone();
-        std::thread::spawn(move || {
-            loop {
-                let i = next_idx.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                if i >= regions.len() {
-                    break;
-                }
-                let region = regions[i].clone();
-                println!("Worker starting region {}", i);
-                let found = solve_tiling_problem(&shapes, &region);
-                let _ = res_tx.send((i, found));
-            }
-        });
-    }
-
-    // Collect results from workers; wait up to 120s per region overall
-    let mut results = vec![false; region_count];
-    for _ in 0..region_count {
-        match res_rx.recv_timeout(Duration::from_secs(60)) {
-            Ok((index, found)) => {
-                results[index] = found;
-                if found {
-                    println!("{} - Solution found ", index);
-                } else {
-                    println!("{} - No solution", index);
-                }
-            }
-            Err(_) => {
-                println!("Timed out ");
-            }
-        }
-    }
-
-    results.into_iter().map(|b| if b { 1 } else { 0 }).sum()
-}
-
-fn part2(problem: &Problem) -> i32 {
-    // TODO: Implement Part 2 solution
-    0
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn sample_input_path() -> String {
-        // Match the same sample path used by main (relative to crate dir)
-        format!(
-            "{}/../../../challenges/day12/input-sample.txt",
-            env!("CARGO_MANIFEST_DIR")
-        )
-    }
-
-    #[test]
-    fn region0_is_solvable() {
-        let input = read_input(&sample_input_path());
-        let problem = parse_problem(&input);
-        assert!(solve_tiling_problem(&problem.shapes, &problem.regions[0]));
-    }
-
-    #[test]
-    fn region1_is_solvable() {
-        let input = read_input(&sample_input_path());
-        let problem = parse_problem(&input);
-        assert!(solve_tiling_problem(&problem.shapes, &problem.regions[1]));
-    }
-
-    #[ignore]
-    #[test]
-    fn region2_is_not_solvable() {
-        let input = read_input(&sample_input_path());
-        let problem = parse_problem(&input);
-        assert!(!solve_tiling_problem(&problem.shapes, &problem.regions[2]));
-    }
-
-    #[test]
-    fn region2_timeout_test() {
-        let input = read_input(&sample_input_path());
-        let problem = parse_problem(&input);
-
-        let (tx, rx) = channel();
-        let shapes = problem.shapes.clone();
-        let region = problem.regions[2].clone();
-        std::thread::spawn(move || {
-            let res = solve_tiling_problem(&shapes, &region);
-            let _ = tx.send(res);
-        });
-
-        // Wait up to 15 seconds for a solver result; if it times out, consider that a pass.
-        match rx.recv_timeout(Duration::from_secs(15)) {
-            Ok(found) => assert!(!found, "region2 unexpectedly had a solution"),
-            Err(_) => {
-                println!("region2 solver timed out after 15s (treated as pass)");
-            }
+            log::trace!("  {:?}", o);
         }
     }
+    aoc_core::run_with_options::<Day12>(&options, &input);
 }